@@ -0,0 +1,186 @@
+use anyhow::Result;
+use anyhow::bail;
+use biome_json_parser::JsonParserOptions;
+use biome_json_parser::parse_json;
+use biome_json_syntax::AnyJsonValue;
+use dprint_core::configuration::ConfigKeyMap;
+use dprint_core::configuration::ConfigKeyValue;
+
+use crate::format_text::get_diagnostics_message;
+
+/// The options of the formatter sections of a Biome configuration file
+/// (`formatter` or `<language>.formatter`) mapped to their dprint key and the
+/// general dprint key the dprint key falls back to, if any.
+///
+/// Language specific options come after the top level ones so they win
+/// when both map to the same dprint key.
+const BIOME_FORMATTER_KEYS: &[(&str, &str, &str, Option<&str>)] = &[
+  ("formatter", "indentStyle", "indentStyle", None),
+  ("formatter", "indentWidth", "indentWidth", None),
+  ("formatter", "lineWidth", "lineWidth", None),
+  ("formatter", "lineEnding", "lineEnding", None),
+  ("formatter", "bracketSpacing", "bracketSpacing", None),
+  ("formatter", "bracketSameLine", "bracketSameLine", None),
+  ("formatter", "expand", "expand", None),
+  ("formatter", "attributePosition", "attributePosition", None),
+  ("javascript", "enabled", "javascript.enabled", None),
+  ("javascript", "enabled", "typescript.enabled", None),
+  (
+    "javascript",
+    "indentStyle",
+    "javascript.indentStyle",
+    Some("indentStyle"),
+  ),
+  (
+    "javascript",
+    "indentWidth",
+    "javascript.indentWidth",
+    Some("indentWidth"),
+  ),
+  ("javascript", "lineWidth", "javascript.lineWidth", Some("lineWidth")),
+  ("javascript", "lineEnding", "javascript.lineEnding", Some("lineEnding")),
+  ("javascript", "quoteStyle", "javascript.quoteStyle", Some("quoteStyle")),
+  ("javascript", "jsxQuoteStyle", "jsxQuoteStyle", None),
+  ("javascript", "quoteProperties", "quoteProperties", None),
+  ("javascript", "trailingCommas", "trailingCommas", None),
+  ("javascript", "semicolons", "semicolons", None),
+  ("javascript", "arrowParentheses", "arrowParentheses", None),
+  ("javascript", "bracketSameLine", "bracketSameLine", None),
+  (
+    "javascript",
+    "bracketSpacing",
+    "javascript.bracketSpacing",
+    Some("bracketSpacing"),
+  ),
+  ("javascript", "expand", "javascript.expand", Some("expand")),
+  (
+    "javascript",
+    "attributePosition",
+    "javascript.attributePosition",
+    Some("attributePosition"),
+  ),
+  (
+    "javascript",
+    "operatorLinebreak",
+    "javascript.operatorLinebreak",
+    Some("operatorLinebreak"),
+  ),
+  ("json", "enabled", "json.enabled", None),
+  ("json", "indentStyle", "json.indentStyle", Some("indentStyle")),
+  ("json", "indentWidth", "json.indentWidth", Some("indentWidth")),
+  ("json", "lineWidth", "json.lineWidth", Some("lineWidth")),
+  ("json", "lineEnding", "json.lineEnding", Some("lineEnding")),
+  ("json", "expand", "json.expand", Some("expand")),
+  ("json", "trailingCommas", "json.trailingCommas", None),
+  ("json", "bracketSpacing", "json.bracketSpacing", None),
+  ("css", "enabled", "css.enabled", None),
+  ("css", "indentStyle", "css.indentStyle", Some("indentStyle")),
+  ("css", "indentWidth", "css.indentWidth", Some("indentWidth")),
+  ("css", "lineWidth", "css.lineWidth", Some("lineWidth")),
+  ("css", "lineEnding", "css.lineEnding", Some("lineEnding")),
+  ("css", "quoteStyle", "css.quoteStyle", Some("quoteStyle")),
+  ("graphql", "enabled", "graphql.enabled", None),
+  ("graphql", "indentStyle", "graphql.indentStyle", Some("indentStyle")),
+  ("graphql", "indentWidth", "graphql.indentWidth", Some("indentWidth")),
+  ("graphql", "lineWidth", "graphql.lineWidth", Some("lineWidth")),
+  ("graphql", "lineEnding", "graphql.lineEnding", Some("lineEnding")),
+  ("graphql", "quoteStyle", "graphql.quoteStyle", Some("quoteStyle")),
+  (
+    "graphql",
+    "bracketSpacing",
+    "graphql.bracketSpacing",
+    Some("bracketSpacing"),
+  ),
+];
+
+/// Parses the text of a biome.json or biome.jsonc file into the object to
+/// provide as the `biomeConfig` key to `resolve_config`, where its formatter
+/// sections fill in the options not specified in the dprint configuration.
+pub fn parse_biome_config(text: &str) -> Result<ConfigKeyMap> {
+  let tree = parse_json(
+    text,
+    JsonParserOptions {
+      allow_comments: true,
+      allow_trailing_commas: true,
+      allow_metavariables: false,
+    },
+  );
+  if tree.has_errors() {
+    bail!("{}", get_diagnostics_message(tree.into_diagnostics()));
+  }
+  match tree
+    .tree()
+    .value()
+    .ok()
+    .and_then(|value| json_value_to_config_value(&value))
+  {
+    Some(ConfigKeyValue::Object(biome_config)) => Ok(biome_config),
+    _ => bail!("Expected the Biome configuration to be an object."),
+  }
+}
+
+/// Maps the formatter sections of a Biome configuration to the dprint keys
+/// that aren't set by the dprint configuration, either directly or through
+/// the general key they fall back to.
+///
+/// This makes the Biome configuration a lower layer than every dprint key,
+/// so a Biome `javascript.formatter.lineWidth` doesn't win over a dprint `lineWidth`.
+pub(crate) fn get_biome_config_keys(biome_config: &ConfigKeyMap, dprint_config: &ConfigKeyMap) -> ConfigKeyMap {
+  let mut config = ConfigKeyMap::new();
+  for (section_name, biome_key, dprint_key, general_key) in BIOME_FORMATTER_KEYS {
+    if dprint_config.contains_key(*dprint_key) || general_key.is_some_and(|key| dprint_config.contains_key(key)) {
+      continue;
+    }
+    let section = match *section_name {
+      "formatter" => biome_config.get("formatter"),
+      _ => match biome_config.get(*section_name) {
+        Some(ConfigKeyValue::Object(language)) => language.get("formatter"),
+        _ => None,
+      },
+    };
+    if let Some(ConfigKeyValue::Object(section)) = section
+      && let Some(value) = section.get(*biome_key)
+    {
+      config.insert(dprint_key.to_string(), value.clone());
+    }
+  }
+  config
+}
+
+fn json_value_to_config_value(value: &AnyJsonValue) -> Option<ConfigKeyValue> {
+  Some(match value {
+    AnyJsonValue::JsonObjectValue(object) => {
+      let mut values = ConfigKeyMap::new();
+      for member in object.json_member_list().iter().flatten() {
+        let Ok(name) = member.name() else {
+          continue;
+        };
+        let Some(name) = name
+          .as_json_member_name()
+          .and_then(|name| name.inner_string_text().ok())
+        else {
+          continue;
+        };
+        if let Some(value) = member.value().ok().and_then(|value| json_value_to_config_value(&value)) {
+          values.insert(name.text().to_string(), value);
+        }
+      }
+      ConfigKeyValue::Object(values)
+    }
+    AnyJsonValue::JsonArrayValue(array) => ConfigKeyValue::Array(
+      array
+        .elements()
+        .iter()
+        .flatten()
+        .filter_map(|value| json_value_to_config_value(&value))
+        .collect(),
+    ),
+    AnyJsonValue::JsonStringValue(value) => ConfigKeyValue::String(value.inner_string_text().ok()?.text().to_string()),
+    AnyJsonValue::JsonNumberValue(value) => {
+      ConfigKeyValue::Number(value.value_token().ok()?.text_trimmed().parse().ok()?)
+    }
+    AnyJsonValue::JsonBooleanValue(value) => ConfigKeyValue::Bool(value.value_token().ok()?.text_trimmed() == "true"),
+    AnyJsonValue::JsonNullValue(_) => ConfigKeyValue::Null,
+    AnyJsonValue::JsonBogusValue(_) => return None,
+  })
+}
@@ -1,6 +1,10 @@
 use dprint_core::configuration::ParseConfigurationError;
 use dprint_core::generate_str_to_from;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::overrides::OverridesResolver;
 
 #[derive(Clone, PartialEq, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -8,9 +12,11 @@ pub enum LineEnding {
   Lf,
   Cr,
   Crlf,
+  /// Uses the line ending found most in each file.
+  Auto,
 }
 
-generate_str_to_from![LineEnding, [Lf, "lf"], [Cr, "cr"], [Crlf, "crlf"]];
+generate_str_to_from![LineEnding, [Lf, "lf"], [Cr, "cr"], [Crlf, "crlf"], [Auto, "auto"]];
 
 #[derive(Clone, PartialEq, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -67,28 +73,125 @@ pub enum TrailingComma {
 
 generate_str_to_from![TrailingComma, [All, "all"], [Es5, "es5"], [None, "none"]];
 
+#[derive(Clone, PartialEq, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JsonTrailingCommas {
+  None,
+  All,
+}
+
+generate_str_to_from![JsonTrailingCommas, [None, "none"], [All, "all"]];
+
+#[derive(Clone, PartialEq, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Expand {
+  Auto,
+  Always,
+  Never,
+}
+
+generate_str_to_from![Expand, [Auto, "auto"], [Always, "always"], [Never, "never"]];
+
+#[derive(Clone, PartialEq, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OperatorLinebreak {
+  After,
+  Before,
+}
+
+generate_str_to_from![OperatorLinebreak, [After, "after"], [Before, "before"]];
+
+#[derive(Clone, PartialEq, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SourceType {
+  Module,
+  Script,
+  Auto,
+}
+
+generate_str_to_from![SourceType, [Module, "module"], [Script, "script"], [Auto, "auto"]];
+
+#[derive(Clone, PartialEq, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AttributePosition {
+  Auto,
+  Multiline,
+}
+
+generate_str_to_from![AttributePosition, [Auto, "auto"], [Multiline, "multiline"]];
+
+#[derive(Clone, PartialEq, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WhitespaceSensitivity {
+  Css,
+  Strict,
+  Ignore,
+}
+
+generate_str_to_from![
+  WhitespaceSensitivity,
+  [Css, "css"],
+  [Strict, "strict"],
+  [Ignore, "ignore"]
+];
+
+/// Configuration keys that were renamed, mapped to their new name.
+///
+/// The old keys are still accepted and `dprint config update` moves them to
+/// the new key.
+pub const RENAMED_KEYS: [(&str, &str); 4] = [
+  ("indentSize", "indentWidth"),
+  ("javascript.indentSize", "javascript.indentWidth"),
+  ("json.indentSize", "json.indentWidth"),
+  ("trailingComma", "trailingCommas"),
+];
+
 #[derive(Default, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Configuration {
   pub line_ending: Option<LineEnding>,
   pub css_enabled: Option<bool>,
+  pub css_line_ending: Option<LineEnding>,
   pub css_indent_style: Option<IndentStyle>,
   pub css_indent_width: Option<u8>,
   pub css_line_width: Option<u16>,
   pub css_quote_style: Option<QuoteStyle>,
   pub graphql_enabled: Option<bool>,
+  pub graphql_line_ending: Option<LineEnding>,
   pub graphql_indent_style: Option<IndentStyle>,
   pub graphql_indent_width: Option<u8>,
   pub graphql_line_width: Option<u16>,
   pub graphql_quote_style: Option<QuoteStyle>,
   pub graphql_bracket_spacing: Option<bool>,
+  /// Additional file extensions to format as GraphQL.
+  pub graphql_extensions: Vec<String>,
+  pub html_enabled: Option<bool>,
+  pub html_indent_style: Option<IndentStyle>,
+  pub html_indent_width: Option<u8>,
+  pub html_line_width: Option<u16>,
+  pub html_attribute_position: Option<AttributePosition>,
+  pub html_bracket_same_line: Option<bool>,
+  pub html_whitespace_sensitivity: Option<WhitespaceSensitivity>,
+  pub javascript_enabled: Option<bool>,
+  pub typescript_enabled: Option<bool>,
+  pub javascript_line_ending: Option<LineEnding>,
   pub javascript_indent_style: Option<IndentStyle>,
   pub javascript_indent_width: Option<u8>,
   pub javascript_line_width: Option<u16>,
   pub javascript_quote_style: Option<QuoteStyle>,
+  pub javascript_expand: Option<Expand>,
+  pub javascript_attribute_position: Option<AttributePosition>,
+  pub javascript_operator_linebreak: Option<OperatorLinebreak>,
+  pub json_enabled: Option<bool>,
+  pub json_line_ending: Option<LineEnding>,
   pub json_indent_style: Option<IndentStyle>,
   pub json_indent_width: Option<u8>,
   pub json_line_width: Option<u16>,
+  pub json_expand: Option<Expand>,
+  pub json_trailing_commas: Option<JsonTrailingCommas>,
+  pub json_bracket_spacing: Option<bool>,
+  pub json5_quote_style: Option<QuoteStyle>,
+  pub json5_quote_properties: Option<QuoteProperties>,
   pub semicolons: Option<Semicolons>,
   pub jsx_quote_style: Option<QuoteStyle>,
   pub quote_properties: Option<QuoteProperties>,
@@ -97,6 +200,41 @@ pub struct Configuration {
   pub bracket_same_line: Option<bool>,
   pub javascript_bracket_spacing: Option<bool>,
   pub css_css_modules: Option<bool>,
+  pub css_tailwind_directives: Option<bool>,
   pub css_grit_metavariables: Option<bool>,
   pub javascript_grit_metavariables: Option<bool>,
+  pub javascript_jsx_everywhere: Option<bool>,
+  pub javascript_source_type: Option<SourceType>,
+  pub javascript_parser_unsafe_parameter_decorators_enabled: Option<bool>,
+  pub javascript_embedded_css: Option<bool>,
+  pub javascript_embedded_graphql: Option<bool>,
+  /// Tagged template tags and block languages (ex. `<style lang="scss">`)
+  /// mapped to the file extension to format them with in the host.
+  pub embedded_languages: BTreeMap<String, String>,
+  pub vue_enabled: Option<bool>,
+  pub vue_indent_script_and_style: Option<bool>,
+  pub svelte_enabled: Option<bool>,
+  pub svelte_indent_script_and_style: Option<bool>,
+  pub astro_enabled: Option<bool>,
+  pub astro_indent_script_and_style: Option<bool>,
+  /// Use the .editorconfig files of each file for the indentation, line
+  /// width and line ending options that aren't configured (native only).
+  pub editorconfig: Option<bool>,
+  /// The .editorconfig files read when formatting with this configuration.
+  #[cfg(not(target_arch = "wasm32"))]
+  #[serde(skip)]
+  pub(crate) editorconfig_cache: Arc<crate::editorconfig::EditorConfigCache>,
+  pub overrides: Vec<ConfigurationOverride>,
+  /// Resolves the configuration of files matching several overrides.
+  #[serde(skip)]
+  pub(crate) overrides_resolver: Option<Arc<OverridesResolver>>,
+}
+
+/// Configuration used for the files matching any of the `includes` globs.
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigurationOverride {
+  pub includes: Vec<String>,
+  /// The base configuration resolved with the keys of the override.
+  pub config: Configuration,
 }
@@ -1,6 +1,8 @@
+mod biome_config;
 #[allow(clippy::module_inception)]
 mod configuration;
 mod resolve_config;
 
+pub use biome_config::*;
 pub use configuration::*;
 pub use resolve_config::*;
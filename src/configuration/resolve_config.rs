@@ -1,7 +1,19 @@
 use super::Configuration;
+use super::ConfigurationOverride;
 use super::IndentStyle;
 use super::LineEnding;
+use super::RENAMED_KEYS;
+use super::biome_config::get_biome_config_keys;
+use biome_formatter::IndentWidth;
+use biome_formatter::LineWidth;
 use dprint_core::configuration::*;
+use dprint_core::plugins::ConfigChange;
+use dprint_core::plugins::ConfigChangeKind;
+use dprint_core::plugins::ConfigChangePathItem;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::overrides::OverridesResolver;
 
 /// Resolves configuration from a collection of key value strings.
 ///
@@ -30,71 +42,139 @@ pub fn resolve_config(
 ) -> ResolveConfigurationResult<Configuration> {
   let mut diagnostics = Vec::new();
   let mut config = config;
+  migrate_renamed_keys(&mut config);
+  let overrides = config.shift_remove("overrides");
+  // overrides apply the biome configuration after merging their keys, so they take precedence
+  let (overrides, override_keys) = resolve_overrides(overrides, &config, global_config, &mut diagnostics);
+  let overrides_resolver = (!overrides.is_empty()).then(|| {
+    Arc::new(OverridesResolver::new(
+      config.clone(),
+      override_keys,
+      global_config.clone(),
+    ))
+  });
+  apply_biome_config(&mut config, &mut diagnostics);
   let indent_style = get_nullable_value(&mut config, "indentStyle", &mut diagnostics).or(global_config.use_tabs.map(
     |value| match value {
       true => IndentStyle::Tab,
       false => IndentStyle::Space,
     },
   ));
-  let indent_width = get_nullable_value(&mut config, "indentWidth", &mut diagnostics)
-    .or_else(|| get_nullable_value(&mut config, "indentSize", &mut diagnostics))
-    .or(global_config.indent_width);
-  let line_width = get_nullable_value(&mut config, "lineWidth", &mut diagnostics).or(
-    global_config
-      .line_width
-      .map(|l| std::cmp::min(u16::MAX as u32, l) as u16),
-  );
+  let indent_width = get_indent_width_value(&mut config, "indentWidth", &mut diagnostics)
+    .or_else(|| validate_indent_width("indentWidth", global_config.indent_width, &mut diagnostics));
+  let line_width = get_line_width_value(&mut config, "lineWidth", &mut diagnostics).or_else(|| {
+    validate_line_width(
+      "lineWidth",
+      global_config
+        .line_width
+        .map(|l| std::cmp::min(u16::MAX as u32, l) as u16),
+      &mut diagnostics,
+    )
+  });
   let quote_style = get_nullable_value(&mut config, "quoteStyle", &mut diagnostics);
   let jsx_quote_style = get_nullable_value(&mut config, "jsxQuoteStyle", &mut diagnostics);
   let bracket_spacing = get_nullable_value(&mut config, "bracketSpacing", &mut diagnostics);
+  let bracket_same_line = get_nullable_value(&mut config, "bracketSameLine", &mut diagnostics);
+  let expand = get_nullable_value(&mut config, "expand", &mut diagnostics);
+  let attribute_position = get_nullable_value(&mut config, "attributePosition", &mut diagnostics);
+  let operator_linebreak = get_nullable_value(&mut config, "operatorLinebreak", &mut diagnostics);
+  let line_ending =
+    get_nullable_value(&mut config, "lineEnding", &mut diagnostics).or(match global_config.new_line_kind {
+      Some(NewLineKind::CarriageReturnLineFeed) => Some(LineEnding::Crlf),
+      Some(NewLineKind::LineFeed) => Some(LineEnding::Lf),
+      Some(NewLineKind::Auto) => Some(LineEnding::Auto),
+      _ => None,
+    });
   let grit_metavariables: Option<bool> = get_nullable_value(&mut config, "gritMetavariables", &mut diagnostics);
 
   let resolved_config = Configuration {
-    line_ending: get_nullable_value(&mut config, "lineEnding", &mut diagnostics).or(
-      match global_config.new_line_kind {
-        Some(NewLineKind::CarriageReturnLineFeed) => Some(LineEnding::Crlf),
-        Some(NewLineKind::LineFeed) => Some(LineEnding::Lf),
-        _ => None,
-      },
-    ),
+    line_ending,
     css_enabled: get_nullable_value(&mut config, "css.enabled", &mut diagnostics),
-    css_indent_width: get_nullable_value(&mut config, "css.indentWidth", &mut diagnostics).or(indent_width),
-    css_line_width: get_nullable_value(&mut config, "css.lineWidth", &mut diagnostics).or(line_width),
+    css_indent_width: get_indent_width_value(&mut config, "css.indentWidth", &mut diagnostics).or(indent_width),
+    css_line_width: get_line_width_value(&mut config, "css.lineWidth", &mut diagnostics).or(line_width),
     css_quote_style: get_nullable_value(&mut config, "css.quoteStyle", &mut diagnostics).or(quote_style),
+    css_line_ending: get_nullable_value(&mut config, "css.lineEnding", &mut diagnostics).or(line_ending),
     css_indent_style: get_nullable_value(&mut config, "css.indentStyle", &mut diagnostics).or(indent_style),
     graphql_enabled: get_nullable_value(&mut config, "graphql.enabled", &mut diagnostics),
-    graphql_indent_width: get_nullable_value(&mut config, "graphql.indentWidth", &mut diagnostics).or(indent_width),
-    graphql_line_width: get_nullable_value(&mut config, "graphql.lineWidth", &mut diagnostics).or(line_width),
+    graphql_indent_width: get_indent_width_value(&mut config, "graphql.indentWidth", &mut diagnostics).or(indent_width),
+    graphql_line_width: get_line_width_value(&mut config, "graphql.lineWidth", &mut diagnostics).or(line_width),
     graphql_quote_style: get_nullable_value(&mut config, "graphql.quoteStyle", &mut diagnostics).or(quote_style),
+    graphql_line_ending: get_nullable_value(&mut config, "graphql.lineEnding", &mut diagnostics).or(line_ending),
     graphql_indent_style: get_nullable_value(&mut config, "graphql.indentStyle", &mut diagnostics).or(indent_style),
     graphql_bracket_spacing: get_nullable_value(&mut config, "graphql.bracketSpacing", &mut diagnostics)
       .or(bracket_spacing),
+    graphql_extensions: get_string_array_value(&mut config, "graphql.extensions", &mut diagnostics)
+      .into_iter()
+      .map(|ext| ext.trim_start_matches('.').to_lowercase())
+      .collect(),
+    html_enabled: get_nullable_value(&mut config, "html.enabled", &mut diagnostics),
+    html_indent_style: get_nullable_value(&mut config, "html.indentStyle", &mut diagnostics).or(indent_style),
+    html_indent_width: get_indent_width_value(&mut config, "html.indentWidth", &mut diagnostics).or(indent_width),
+    html_line_width: get_line_width_value(&mut config, "html.lineWidth", &mut diagnostics).or(line_width),
+    html_attribute_position: get_nullable_value(&mut config, "html.attributePosition", &mut diagnostics)
+      .or(attribute_position),
+    html_bracket_same_line: get_nullable_value(&mut config, "html.bracketSameLine", &mut diagnostics)
+      .or(bracket_same_line),
+    html_whitespace_sensitivity: get_nullable_value(&mut config, "html.whitespaceSensitivity", &mut diagnostics),
+    javascript_enabled: get_nullable_value(&mut config, "javascript.enabled", &mut diagnostics),
+    typescript_enabled: get_nullable_value(&mut config, "typescript.enabled", &mut diagnostics),
+    javascript_line_ending: get_nullable_value(&mut config, "javascript.lineEnding", &mut diagnostics).or(line_ending),
     javascript_indent_style: get_nullable_value(&mut config, "javascript.indentStyle", &mut diagnostics)
       .or(indent_style),
-    javascript_indent_width: get_nullable_value(&mut config, "javascript.indentWidth", &mut diagnostics)
-      .or_else(|| get_nullable_value(&mut config, "javascript.indentSize", &mut diagnostics))
+    javascript_indent_width: get_indent_width_value(&mut config, "javascript.indentWidth", &mut diagnostics)
       .or(indent_width),
-    javascript_line_width: get_nullable_value(&mut config, "javascript.lineWidth", &mut diagnostics).or(line_width),
+    javascript_line_width: get_line_width_value(&mut config, "javascript.lineWidth", &mut diagnostics).or(line_width),
     javascript_quote_style: get_nullable_value(&mut config, "javascript.quoteStyle", &mut diagnostics).or(quote_style),
+    javascript_expand: get_nullable_value(&mut config, "javascript.expand", &mut diagnostics).or(expand),
+    javascript_attribute_position: get_nullable_value(&mut config, "javascript.attributePosition", &mut diagnostics)
+      .or(attribute_position),
+    javascript_operator_linebreak: get_nullable_value(&mut config, "javascript.operatorLinebreak", &mut diagnostics)
+      .or(operator_linebreak),
+    json_enabled: get_nullable_value(&mut config, "json.enabled", &mut diagnostics),
+    json_line_ending: get_nullable_value(&mut config, "json.lineEnding", &mut diagnostics).or(line_ending),
     json_indent_style: get_nullable_value(&mut config, "json.indentStyle", &mut diagnostics).or(indent_style),
-    json_indent_width: get_nullable_value(&mut config, "json.indentWidth", &mut diagnostics)
-      .or_else(|| get_nullable_value(&mut config, "json.indentSize", &mut diagnostics))
-      .or(indent_width),
-    json_line_width: get_nullable_value(&mut config, "json.lineWidth", &mut diagnostics).or(line_width),
+    json_indent_width: get_indent_width_value(&mut config, "json.indentWidth", &mut diagnostics).or(indent_width),
+    json_line_width: get_line_width_value(&mut config, "json.lineWidth", &mut diagnostics).or(line_width),
+    json_expand: get_nullable_value(&mut config, "json.expand", &mut diagnostics).or(expand),
+    json_trailing_commas: get_nullable_value(&mut config, "json.trailingCommas", &mut diagnostics),
+    json_bracket_spacing: get_nullable_value(&mut config, "json.bracketSpacing", &mut diagnostics),
+    json5_quote_style: get_nullable_value(&mut config, "json5.quoteStyle", &mut diagnostics),
+    json5_quote_properties: get_nullable_value(&mut config, "json5.quoteProperties", &mut diagnostics),
     quote_properties: get_nullable_value(&mut config, "quoteProperties", &mut diagnostics),
     semicolons: get_nullable_value(&mut config, "semicolons", &mut diagnostics),
     arrow_parentheses: get_nullable_value(&mut config, "arrowParentheses", &mut diagnostics),
     jsx_quote_style,
-    trailing_commas: get_nullable_value(&mut config, "trailingCommas", &mut diagnostics)
-      .or_else(|| get_nullable_value(&mut config, "trailingComma", &mut diagnostics)),
-    bracket_same_line: get_nullable_value(&mut config, "bracketSameLine", &mut diagnostics),
+    trailing_commas: get_nullable_value(&mut config, "trailingCommas", &mut diagnostics),
+    bracket_same_line,
     javascript_bracket_spacing: get_nullable_value(&mut config, "javascript.bracketSpacing", &mut diagnostics)
       .or(bracket_spacing),
     css_css_modules: get_nullable_value(&mut config, "css.cssModules", &mut diagnostics),
+    css_tailwind_directives: get_nullable_value(&mut config, "css.tailwindDirectives", &mut diagnostics),
     css_grit_metavariables: get_nullable_value(&mut config, "css.gritMetavariables", &mut diagnostics)
       .or(grit_metavariables),
     javascript_grit_metavariables: get_nullable_value(&mut config, "javascript.gritMetavariables", &mut diagnostics)
       .or(grit_metavariables),
+    javascript_jsx_everywhere: get_nullable_value(&mut config, "javascript.jsxEverywhere", &mut diagnostics),
+    javascript_source_type: get_nullable_value(&mut config, "javascript.sourceType", &mut diagnostics),
+    javascript_parser_unsafe_parameter_decorators_enabled: get_nullable_value(
+      &mut config,
+      "javascript.parser.unsafeParameterDecoratorsEnabled",
+      &mut diagnostics,
+    ),
+    javascript_embedded_css: get_nullable_value(&mut config, "javascript.embeddedCss", &mut diagnostics),
+    javascript_embedded_graphql: get_nullable_value(&mut config, "javascript.embeddedGraphql", &mut diagnostics),
+    embedded_languages: get_string_map_value(&mut config, "embeddedLanguages", &mut diagnostics),
+    vue_enabled: get_nullable_value(&mut config, "vue.enabled", &mut diagnostics),
+    vue_indent_script_and_style: get_nullable_value(&mut config, "vue.indentScriptAndStyle", &mut diagnostics),
+    svelte_enabled: get_nullable_value(&mut config, "svelte.enabled", &mut diagnostics),
+    svelte_indent_script_and_style: get_nullable_value(&mut config, "svelte.indentScriptAndStyle", &mut diagnostics),
+    astro_enabled: get_nullable_value(&mut config, "astro.enabled", &mut diagnostics),
+    astro_indent_script_and_style: get_nullable_value(&mut config, "astro.indentScriptAndStyle", &mut diagnostics),
+    editorconfig: get_editorconfig_value(&mut config, &mut diagnostics),
+    #[cfg(not(target_arch = "wasm32"))]
+    editorconfig_cache: Default::default(),
+    overrides,
+    overrides_resolver,
   };
 
   diagnostics.extend(get_unknown_property_diagnostics(config));
@@ -104,3 +184,290 @@ pub fn resolve_config(
     diagnostics,
   }
 }
+
+fn get_indent_width_value(
+  config: &mut ConfigKeyMap,
+  key: &str,
+  diagnostics: &mut Vec<ConfigurationDiagnostic>,
+) -> Option<u8> {
+  let value = get_nullable_value(config, key, diagnostics);
+  validate_indent_width(key, value, diagnostics)
+}
+
+fn get_line_width_value(
+  config: &mut ConfigKeyMap,
+  key: &str,
+  diagnostics: &mut Vec<ConfigurationDiagnostic>,
+) -> Option<u16> {
+  let value = get_nullable_value(config, key, diagnostics);
+  validate_line_width(key, value, diagnostics)
+}
+
+/// Reports when `editorconfig` is enabled in the Wasm plugin, where it has no effect.
+fn get_editorconfig_value(config: &mut ConfigKeyMap, diagnostics: &mut Vec<ConfigurationDiagnostic>) -> Option<bool> {
+  let value = get_nullable_value(config, "editorconfig", diagnostics);
+  if cfg!(target_arch = "wasm32") && value == Some(true) {
+    diagnostics.push(ConfigurationDiagnostic {
+      property_name: "editorconfig".to_string(),
+      message:
+        "Only supported when using the plugin as a Rust crate because the Wasm plugin has no access to the file system."
+          .to_string(),
+    });
+  }
+  value
+}
+
+/// Gets the value when it's within Biome's limits for an indent width.
+fn validate_indent_width(key: &str, value: Option<u8>, diagnostics: &mut Vec<ConfigurationDiagnostic>) -> Option<u8> {
+  validate_range(key, value, IndentWidth::MIN, IndentWidth::MAX, diagnostics)
+}
+
+/// Gets the value when it's within Biome's limits for a line width.
+fn validate_line_width(key: &str, value: Option<u16>, diagnostics: &mut Vec<ConfigurationDiagnostic>) -> Option<u16> {
+  validate_range(key, value, LineWidth::MIN, LineWidth::MAX, diagnostics)
+}
+
+fn validate_range<T: PartialOrd + std::fmt::Display>(
+  key: &str,
+  value: Option<T>,
+  min: T,
+  max: T,
+  diagnostics: &mut Vec<ConfigurationDiagnostic>,
+) -> Option<T> {
+  match value {
+    Some(value) if value < min || value > max => {
+      diagnostics.push(ConfigurationDiagnostic {
+        property_name: key.to_string(),
+        message: format!("Expected a value between {} and {}, but found {}.", min, max, value),
+      });
+      None
+    }
+    value => value,
+  }
+}
+
+/// Moves the values of renamed keys to their new key when it's not specified.
+fn migrate_renamed_keys(config: &mut ConfigKeyMap) {
+  for (old_key, new_key) in RENAMED_KEYS {
+    if !config.contains_key(new_key)
+      && let Some(value) = config.shift_remove(old_key)
+    {
+      config.insert(new_key.to_string(), value);
+    }
+  }
+}
+
+/// Gets the changes that move renamed keys to their new key in the
+/// configuration and in each of its `overrides`.
+///
+/// When both keys are specified, the old key is removed and the new key is kept.
+pub fn get_config_updates(config: &ConfigKeyMap) -> Vec<ConfigChange> {
+  let mut changes = Vec::new();
+  add_renamed_key_changes(config, &[], &mut changes);
+  if let Some(ConfigKeyValue::Array(overrides)) = config.get("overrides") {
+    for (i, value) in overrides.iter().enumerate() {
+      if let ConfigKeyValue::Object(override_config) = value {
+        let path = [
+          ConfigChangePathItem::String("overrides".to_string()),
+          ConfigChangePathItem::Number(i),
+        ];
+        add_renamed_key_changes(override_config, &path, &mut changes);
+      }
+    }
+  }
+  changes
+}
+
+fn add_renamed_key_changes(config: &ConfigKeyMap, path: &[ConfigChangePathItem], changes: &mut Vec<ConfigChange>) {
+  let get_path = |key: &str| {
+    let mut path = path.to_vec();
+    path.push(ConfigChangePathItem::String(key.to_string()));
+    path
+  };
+  for (old_key, new_key) in RENAMED_KEYS {
+    let Some(value) = config.get(old_key) else {
+      continue;
+    };
+    // the new key takes precedence when both are specified
+    if !config.contains_key(new_key) {
+      changes.push(ConfigChange {
+        path: get_path(new_key),
+        kind: ConfigChangeKind::Add(value.clone()),
+      });
+    }
+    changes.push(ConfigChange {
+      path: get_path(old_key),
+      kind: ConfigChangeKind::Remove,
+    });
+  }
+}
+
+/// Resolves each override from the base configuration keys with the keys
+/// of the override taking precedence, also returning the keys of each override.
+fn resolve_overrides(
+  overrides: Option<ConfigKeyValue>,
+  base_config: &ConfigKeyMap,
+  global_config: &GlobalConfiguration,
+  diagnostics: &mut Vec<ConfigurationDiagnostic>,
+) -> (Vec<ConfigurationOverride>, Vec<ConfigKeyMap>) {
+  let values = match overrides {
+    None | Some(ConfigKeyValue::Null) => return (Vec::new(), Vec::new()),
+    Some(ConfigKeyValue::Array(values)) => values,
+    Some(_) => {
+      diagnostics.push(ConfigurationDiagnostic {
+        property_name: "overrides".to_string(),
+        message: "Expected an array.".to_string(),
+      });
+      return (Vec::new(), Vec::new());
+    }
+  };
+
+  let mut overrides = Vec::with_capacity(values.len());
+  let mut override_keys = Vec::with_capacity(values.len());
+  for (i, value) in values.into_iter().enumerate() {
+    let property_name = format!("overrides[{}]", i);
+    let ConfigKeyValue::Object(mut override_config) = value else {
+      diagnostics.push(ConfigurationDiagnostic {
+        property_name,
+        message: "Expected an object.".to_string(),
+      });
+      continue;
+    };
+    let mut override_diagnostics = Vec::new();
+    let includes = get_string_array_value(&mut override_config, "includes", &mut override_diagnostics);
+    if includes.is_empty() && override_diagnostics.is_empty() {
+      override_diagnostics.push(ConfigurationDiagnostic {
+        property_name: "includes".to_string(),
+        message: "Expected at least one glob.".to_string(),
+      });
+    }
+    for include in &includes {
+      if !is_anchored_glob(include.strip_prefix('!').unwrap_or(include)) {
+        override_diagnostics.push(ConfigurationDiagnostic {
+          property_name: "includes".to_string(),
+          message: format!(
+            "Expected the glob to start with **/ or be absolute because globs are matched against absolute file paths (ex. **/{}), but found {}.",
+            include.trim_start_matches('!').trim_start_matches("./"),
+            include
+          ),
+        });
+      }
+    }
+    if override_config.shift_remove("overrides").is_some() {
+      override_diagnostics.push(ConfigurationDiagnostic {
+        property_name: "overrides".to_string(),
+        message: "Overrides cannot be nested.".to_string(),
+      });
+    }
+
+    migrate_renamed_keys(&mut override_config);
+    let keys = override_config.keys().cloned().collect::<Vec<_>>();
+    let mut config = base_config.clone();
+    config.extend(override_config.clone());
+    let result = resolve_config(config, global_config);
+    // only report the diagnostics of the override's keys as the base keys are reported already
+    override_diagnostics.extend(result.diagnostics.into_iter().filter(|diagnostic| {
+      keys
+        .iter()
+        .any(|key| diagnostic.property_name == *key || diagnostic.property_name.starts_with(&format!("{}.", key)))
+    }));
+    diagnostics.extend(
+      override_diagnostics
+        .into_iter()
+        .map(|diagnostic| ConfigurationDiagnostic {
+          property_name: format!("{}.{}", property_name, diagnostic.property_name),
+          message: diagnostic.message,
+        }),
+    );
+    overrides.push(ConfigurationOverride {
+      includes,
+      config: result.config,
+    });
+    override_keys.push(override_config);
+  }
+  (overrides, override_keys)
+}
+
+/// Gets if the glob matches in any directory (`**/`) or is an absolute path,
+/// since relative globs would never match the absolute paths dprint provides.
+fn is_anchored_glob(glob: &str) -> bool {
+  let glob = glob.replace('\\', "/");
+  let mut chars = glob.chars();
+  let is_drive_path = matches!(
+    (chars.next(), chars.next(), chars.next()),
+    (Some(letter), Some(':'), Some('/')) if letter.is_ascii_alphabetic()
+  );
+  glob == "**" || glob.starts_with("**/") || glob.starts_with('/') || is_drive_path
+}
+
+/// Fills in the keys not specified in the dprint configuration with the
+/// ones mapped from the Biome configuration in the `biomeConfig` key.
+fn apply_biome_config(config: &mut ConfigKeyMap, diagnostics: &mut Vec<ConfigurationDiagnostic>) {
+  match config.shift_remove("biomeConfig") {
+    None | Some(ConfigKeyValue::Null) => {}
+    Some(ConfigKeyValue::Object(biome_config)) => {
+      let biome_config_keys = get_biome_config_keys(&biome_config, config);
+      config.extend(biome_config_keys);
+    }
+    Some(_) => diagnostics.push(ConfigurationDiagnostic {
+      property_name: "biomeConfig".to_string(),
+      message: "Expected an object.".to_string(),
+    }),
+  }
+}
+
+fn get_string_map_value(
+  config: &mut ConfigKeyMap,
+  key: &str,
+  diagnostics: &mut Vec<ConfigurationDiagnostic>,
+) -> BTreeMap<String, String> {
+  let mut result = BTreeMap::new();
+  match config.shift_remove(key) {
+    None | Some(ConfigKeyValue::Null) => {}
+    Some(ConfigKeyValue::Object(values)) => {
+      for (name, value) in values {
+        match value {
+          ConfigKeyValue::String(value) => {
+            result.insert(name, value);
+          }
+          _ => diagnostics.push(ConfigurationDiagnostic {
+            property_name: format!("{}.{}", key, name),
+            message: "Expected a string.".to_string(),
+          }),
+        }
+      }
+    }
+    Some(_) => diagnostics.push(ConfigurationDiagnostic {
+      property_name: key.to_string(),
+      message: "Expected an object.".to_string(),
+    }),
+  }
+  result
+}
+
+fn get_string_array_value(
+  config: &mut ConfigKeyMap,
+  key: &str,
+  diagnostics: &mut Vec<ConfigurationDiagnostic>,
+) -> Vec<String> {
+  let mut result = Vec::new();
+  match config.shift_remove(key) {
+    None | Some(ConfigKeyValue::Null) => {}
+    Some(ConfigKeyValue::Array(values)) => {
+      for value in values {
+        match value {
+          ConfigKeyValue::String(value) => result.push(value),
+          _ => diagnostics.push(ConfigurationDiagnostic {
+            property_name: key.to_string(),
+            message: "Expected an array of strings.".to_string(),
+          }),
+        }
+      }
+    }
+    Some(_) => diagnostics.push(ConfigurationDiagnostic {
+      property_name: key.to_string(),
+      message: "Expected an array.".to_string(),
+    }),
+  }
+  result
+}
@@ -0,0 +1,228 @@
+use biome_formatter::IndentWidth;
+use biome_formatter::LineWidth;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::configuration::Configuration;
+use crate::configuration::IndentStyle;
+use crate::configuration::LineEnding;
+use crate::overrides::expand_braces;
+
+/// The properties of the .editorconfig files that apply to a file.
+#[derive(Default)]
+struct EditorConfigProperties {
+  indent_style: Option<IndentStyle>,
+  indent_width: Option<u8>,
+  line_width: Option<u16>,
+  line_ending: Option<LineEnding>,
+}
+
+struct EditorConfigSection {
+  glob: String,
+  properties: Vec<(String, String)>,
+}
+
+/// A parsed .editorconfig file.
+struct EditorConfigFile {
+  is_root: bool,
+  sections: Vec<EditorConfigSection>,
+}
+
+/// The parsed .editorconfig files of each directory, or `None` when the
+/// directory has no .editorconfig file, so they're only read once.
+#[derive(Default)]
+pub struct EditorConfigCache {
+  files: Mutex<HashMap<PathBuf, Option<Arc<EditorConfigFile>>>>,
+}
+
+impl EditorConfigCache {
+  fn get_file(&self, dir: &Path) -> Option<Arc<EditorConfigFile>> {
+    if let Some(file) = self.files.lock().unwrap().get(dir) {
+      return file.clone();
+    }
+    let file = std::fs::read_to_string(dir.join(".editorconfig"))
+      .ok()
+      .map(|text| Arc::new(parse_editorconfig(&text)));
+    self.files.lock().unwrap().insert(dir.to_path_buf(), file.clone());
+    file
+  }
+}
+
+/// Fills in the indentation, line width and line ending options that aren't
+/// configured with the values of the .editorconfig files of the file path
+/// when `editorconfig` is enabled.
+pub fn apply_editorconfig<'a>(file_path: &Path, config: &'a Configuration) -> Cow<'a, Configuration> {
+  if config.editorconfig != Some(true) {
+    return Cow::Borrowed(config);
+  }
+  let properties = get_editorconfig_properties(file_path, &config.editorconfig_cache);
+  let indent_style = |value: Option<IndentStyle>| value.or(properties.indent_style);
+  let indent_width = |value: Option<u8>| value.or(properties.indent_width);
+  let line_width = |value: Option<u16>| value.or(properties.line_width);
+  let line_ending = |value: Option<LineEnding>| value.or(properties.line_ending);
+  Cow::Owned(Configuration {
+    line_ending: line_ending(config.line_ending),
+    css_line_ending: line_ending(config.css_line_ending),
+    css_indent_style: indent_style(config.css_indent_style),
+    css_indent_width: indent_width(config.css_indent_width),
+    css_line_width: line_width(config.css_line_width),
+    graphql_line_ending: line_ending(config.graphql_line_ending),
+    graphql_indent_style: indent_style(config.graphql_indent_style),
+    graphql_indent_width: indent_width(config.graphql_indent_width),
+    graphql_line_width: line_width(config.graphql_line_width),
+    html_indent_style: indent_style(config.html_indent_style),
+    html_indent_width: indent_width(config.html_indent_width),
+    html_line_width: line_width(config.html_line_width),
+    javascript_line_ending: line_ending(config.javascript_line_ending),
+    javascript_indent_style: indent_style(config.javascript_indent_style),
+    javascript_indent_width: indent_width(config.javascript_indent_width),
+    javascript_line_width: line_width(config.javascript_line_width),
+    json_line_ending: line_ending(config.json_line_ending),
+    json_indent_style: indent_style(config.json_indent_style),
+    json_indent_width: indent_width(config.json_indent_width),
+    json_line_width: line_width(config.json_line_width),
+    ..config.clone()
+  })
+}
+
+fn get_editorconfig_properties(file_path: &Path, cache: &EditorConfigCache) -> EditorConfigProperties {
+  // the files closest to the file path take precedence, so apply them last
+  let mut files = Vec::new();
+  for dir in file_path.ancestors().skip(1) {
+    let Some(file) = cache.get_file(dir) else {
+      continue;
+    };
+    let is_root = file.is_root;
+    files.push((dir, file));
+    if is_root {
+      break;
+    }
+  }
+
+  let mut properties = EditorConfigProperties::default();
+  let mut indent_size = None;
+  let mut tab_width = None;
+  for (dir, file) in files.iter().rev() {
+    let Some(relative_path) = file_path.strip_prefix(dir).ok().and_then(|path| path.to_str()) else {
+      continue;
+    };
+    let relative_path = relative_path.replace('\\', "/");
+    for section in &file.sections {
+      if !matches_section_glob(&section.glob, &relative_path) {
+        continue;
+      }
+      for (key, value) in &section.properties {
+        match key.as_str() {
+          "indent_style" => {
+            properties.indent_style = match value.as_str() {
+              "tab" => Some(IndentStyle::Tab),
+              "space" => Some(IndentStyle::Space),
+              _ => properties.indent_style,
+            }
+          }
+          "indent_size" if value == "tab" || parse_indent_width(value).is_some() => indent_size = Some(value.clone()),
+          "tab_width" => tab_width = parse_indent_width(value).or(tab_width),
+          "max_line_length" => {
+            properties.line_width = match value.as_str() {
+              "off" => None,
+              value => parse_line_width(value).or(properties.line_width),
+            }
+          }
+          "end_of_line" => {
+            properties.line_ending = match value.as_str() {
+              "lf" => Some(LineEnding::Lf),
+              "crlf" => Some(LineEnding::Crlf),
+              "cr" => Some(LineEnding::Cr),
+              _ => properties.line_ending,
+            }
+          }
+          _ => {}
+        }
+      }
+    }
+  }
+  properties.indent_width = match indent_size.as_deref() {
+    Some("tab") => tab_width,
+    Some(value) => parse_indent_width(value),
+    None => tab_width,
+  };
+  properties
+}
+
+/// Widths outside the range supported by Biome are ignored, as they'd
+/// otherwise fail formatting every file.
+fn parse_indent_width(value: &str) -> Option<u8> {
+  value
+    .parse()
+    .ok()
+    .filter(|width| (IndentWidth::MIN..=IndentWidth::MAX).contains(width))
+}
+
+fn parse_line_width(value: &str) -> Option<u16> {
+  value
+    .parse()
+    .ok()
+    .filter(|width| (LineWidth::MIN..=LineWidth::MAX).contains(width))
+}
+
+/// Parses the text of an .editorconfig file.
+fn parse_editorconfig(text: &str) -> EditorConfigFile {
+  let mut is_root = false;
+  let mut sections: Vec<EditorConfigSection> = Vec::new();
+  for line in text.lines() {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+      continue;
+    }
+    if let Some(glob) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+      sections.push(EditorConfigSection {
+        glob: glob.to_string(),
+        properties: Vec::new(),
+      });
+      continue;
+    }
+    let Some((key, value)) = line.split_once('=') else {
+      continue;
+    };
+    let key = key.trim().to_lowercase();
+    let value = value.trim().to_lowercase();
+    match sections.last_mut() {
+      Some(section) => section.properties.push((key, value)),
+      // properties before any section are preamble properties
+      None => is_root = is_root || (key == "root" && value == "true"),
+    }
+  }
+  EditorConfigFile { is_root, sections }
+}
+
+/// Globs without a slash match the file name in any directory, while the
+/// other globs match the path relative to the .editorconfig file.
+fn matches_section_glob(glob: &str, relative_path: &str) -> bool {
+  let glob = match glob.strip_prefix('/') {
+    Some(glob) => glob.to_string(),
+    None if glob.contains('/') => glob.to_string(),
+    None => format!("**/{}", glob),
+  };
+  let path = relative_path.chars().collect::<Vec<_>>();
+  expand_braces(&glob).iter().any(|glob| {
+    let glob = glob.chars().collect::<Vec<_>>();
+    // `**/` also matches the files directly in the directory
+    matches_glob(&glob, &path) || (glob.starts_with(&['*', '*', '/']) && matches_glob(&glob[3..], &path))
+  })
+}
+
+fn matches_glob(glob: &[char], path: &[char]) -> bool {
+  match glob.first() {
+    None => path.is_empty(),
+    Some('*') if glob.get(1) == Some(&'*') => (0..=path.len()).any(|i| matches_glob(&glob[2..], &path[i..])),
+    Some('*') => (0..=path.len())
+      .take_while(|i| *i == 0 || path[i - 1] != '/')
+      .any(|i| matches_glob(&glob[1..], &path[i..])),
+    Some('?') => path.first().is_some_and(|c| *c != '/') && matches_glob(&glob[1..], &path[1..]),
+    Some(c) => path.first() == Some(c) && matches_glob(&glob[1..], &path[1..]),
+  }
+}
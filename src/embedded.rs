@@ -0,0 +1,354 @@
+use anyhow::Result;
+use biome_css_syntax::CssFileSource;
+use biome_graphql_syntax::GraphqlFileSource;
+use biome_js_syntax::AnyJsTemplateElement;
+use biome_js_syntax::JsFileSource;
+use biome_js_syntax::JsLanguage;
+use biome_js_syntax::JsTemplateExpression;
+use biome_rowan::AstNode;
+use biome_rowan::SyntaxTriviaPiece;
+use biome_rowan::TextRange;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::configuration::Configuration;
+use crate::configuration::IndentStyle;
+use crate::configuration::LineEnding;
+use crate::format_text::HostFormatter;
+use crate::format_text::build_css_options;
+use crate::format_text::build_graphql_options;
+use crate::format_text::css_parser_options;
+use crate::format_text::js_parser_options;
+
+const PLACEHOLDER_PREFIX: &str = "__dprint_biome_placeholder_";
+const CSS_WRAPPER_SELECTOR: &str = "__dprint_biome_wrapper__";
+
+#[derive(Clone, PartialEq)]
+enum EmbeddedLanguage {
+  Css,
+  Graphql,
+  /// Formatted by the host as a file with this extension.
+  Host(String),
+}
+
+struct EmbeddedTemplate {
+  language: EmbeddedLanguage,
+  /// Range of the text between the backticks.
+  content_range: TextRange,
+  /// Ranges of the `${}` interpolations within the content.
+  interpolation_ranges: Vec<TextRange>,
+}
+
+pub fn has_embedded_languages(config: &Configuration) -> bool {
+  config.javascript_embedded_css == Some(true)
+    || config.javascript_embedded_graphql == Some(true)
+    || !config.embedded_languages.is_empty()
+}
+
+/// Formats the contents of tagged templates that hold another language
+/// (ex. styled-components' css or Apollo's gql) within already formatted
+/// JavaScript code.
+///
+/// Templates that can't be formatted safely are left as-is.
+pub fn format_embedded_templates(
+  code: &str,
+  file_path: &Path,
+  syntax: JsFileSource,
+  config: &Configuration,
+  format_with_host: &mut HostFormatter,
+) -> Result<String> {
+  let templates = collect_templates(code, syntax, config);
+  if templates.is_empty() {
+    return Ok(code.to_string());
+  }
+
+  // format the embedded code with the same indentation as the surrounding code
+  let embedded_config = Configuration {
+    css_indent_style: config.javascript_indent_style,
+    css_indent_width: config.javascript_indent_width,
+    // the newlines are replaced when indenting the formatted content
+    css_line_ending: Some(LineEnding::Lf),
+    graphql_line_ending: Some(LineEnding::Lf),
+    ..config.clone()
+  };
+  let newline = get_newline(code);
+  let indent_unit = get_indent_unit(config);
+  let mut result = String::with_capacity(code.len());
+  let mut last_end = 0;
+  for template in templates {
+    let start = usize::from(template.content_range.start());
+    let end = usize::from(template.content_range.end());
+    let can_format_interpolations = match &template.language {
+      EmbeddedLanguage::Css => true,
+      EmbeddedLanguage::Graphql => has_interpolations_on_own_lines(code, &template),
+      // there's no way to know what placeholder would be valid in the other language
+      EmbeddedLanguage::Host(_) => template.interpolation_ranges.is_empty(),
+    };
+    if !can_format_interpolations {
+      continue;
+    }
+    let (text, interpolations) = get_text_with_placeholders(code, &template);
+    if text.trim().is_empty() || text.contains('\\') {
+      continue;
+    }
+    let formatted = match &template.language {
+      EmbeddedLanguage::Css => format_css_template(&text, &embedded_config)?,
+      EmbeddedLanguage::Graphql => format_graphql_template(&text, &embedded_config)?,
+      EmbeddedLanguage::Host(extension) => {
+        format_with_host(&get_embedded_file_path(file_path, extension), &dedent(&text))?
+      }
+    };
+    let Some(formatted) = formatted else {
+      continue;
+    };
+    let indent = get_line_indent(code, start);
+    let content_indent = match &template.language {
+      // already indented within the wrapper rule
+      EmbeddedLanguage::Css => indent.to_string(),
+      EmbeddedLanguage::Graphql | EmbeddedLanguage::Host(_) => format!("{}{}", indent, indent_unit),
+    };
+    let indented = indent_block_content(&formatted, &content_indent, indent, newline);
+    let Some(restored) = restore_placeholders(indented, &template.language, &interpolations) else {
+      continue;
+    };
+    result.push_str(&code[last_end..start]);
+    result.push_str(&restored);
+    last_end = end;
+  }
+  result.push_str(&code[last_end..]);
+  Ok(result)
+}
+
+fn collect_templates(code: &str, syntax: JsFileSource, config: &Configuration) -> Vec<EmbeddedTemplate> {
+  let tree = biome_js_parser::parse(code, syntax, js_parser_options(config));
+  if tree.has_errors() {
+    return Vec::new();
+  }
+
+  let mut templates: Vec<EmbeddedTemplate> = Vec::new();
+  for template in tree.syntax().descendants().filter_map(JsTemplateExpression::cast) {
+    let Some(language) = get_template_language(&template, config) else {
+      continue;
+    };
+    let (Ok(l_tick), Ok(r_tick)) = (template.l_tick_token(), template.r_tick_token()) else {
+      continue;
+    };
+    let content_range = TextRange::new(l_tick.text_trimmed_range().end(), r_tick.text_trimmed_range().start());
+    // descendants are visited in order, so this skips templates within the interpolation of another one
+    if let Some(last) = templates.last() {
+      if last.content_range.contains_range(content_range) {
+        continue;
+      }
+    }
+    let interpolation_ranges = template
+      .elements()
+      .iter()
+      .filter_map(|element| match element {
+        AnyJsTemplateElement::JsTemplateElement(element) => Some(element.syntax().text_trimmed_range()),
+        AnyJsTemplateElement::JsTemplateChunkElement(_) => None,
+      })
+      .collect();
+    templates.push(EmbeddedTemplate {
+      language,
+      content_range,
+      interpolation_ranges,
+    });
+  }
+  templates
+}
+
+fn get_template_language(template: &JsTemplateExpression, config: &Configuration) -> Option<EmbeddedLanguage> {
+  match template.tag() {
+    Some(tag) => {
+      let tag_text = tag.syntax().text_trimmed().to_string();
+      if let Some(extension) = config.embedded_languages.get(&tag_text) {
+        Some(EmbeddedLanguage::Host(extension.clone()))
+      } else if config.javascript_embedded_css == Some(true) && is_css_tag(&tag_text) {
+        Some(EmbeddedLanguage::Css)
+      } else if config.javascript_embedded_graphql == Some(true) && is_graphql_tag(&tag_text) {
+        Some(EmbeddedLanguage::Graphql)
+      } else {
+        None
+      }
+    }
+    None => {
+      if config.javascript_embedded_graphql == Some(true) && has_graphql_comment(template) {
+        Some(EmbeddedLanguage::Graphql)
+      } else {
+        None
+      }
+    }
+  }
+}
+
+fn is_css_tag(tag: &str) -> bool {
+  matches!(tag, "css" | "createGlobalStyle" | "keyframes" | "injectGlobal")
+    || tag.starts_with("styled.")
+    || tag.starts_with("styled(")
+}
+
+fn is_graphql_tag(tag: &str) -> bool {
+  matches!(tag, "gql" | "graphql")
+}
+
+/// Gets if the template is preceded by a `/* GraphQL */` comment.
+fn has_graphql_comment(template: &JsTemplateExpression) -> bool {
+  let Ok(l_tick) = template.l_tick_token() else {
+    return false;
+  };
+  let is_graphql_comment =
+    |piece: SyntaxTriviaPiece<JsLanguage>| piece.is_comments() && piece.text() == "/* GraphQL */";
+  // a comment on the same line as the previous token is that token's trailing trivia
+  l_tick.leading_trivia().pieces().any(is_graphql_comment)
+    || l_tick
+      .prev_token()
+      .is_some_and(|token| token.trailing_trivia().pieces().any(is_graphql_comment))
+}
+
+/// GraphQL placeholders are comments, so interpolations may only appear
+/// between definitions or selections on their own line.
+fn has_interpolations_on_own_lines(code: &str, template: &EmbeddedTemplate) -> bool {
+  template.interpolation_ranges.iter().all(|range| {
+    let start = usize::from(range.start());
+    let end = usize::from(range.end());
+    let line_start = code[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let line_end = code[end..].find('\n').map(|i| end + i).unwrap_or(code.len());
+    code[line_start..start].trim().is_empty() && code[end..line_end].trim().is_empty()
+  })
+}
+
+/// Gets the template's content with each interpolation replaced by a
+/// placeholder, along with the original text of the interpolations.
+fn get_text_with_placeholders<'a>(code: &'a str, template: &EmbeddedTemplate) -> (String, Vec<&'a str>) {
+  let mut text = String::new();
+  let mut interpolations = Vec::with_capacity(template.interpolation_ranges.len());
+  let mut last_end = usize::from(template.content_range.start());
+  for (i, range) in template.interpolation_ranges.iter().enumerate() {
+    let start = usize::from(range.start());
+    let end = usize::from(range.end());
+    text.push_str(&code[last_end..start]);
+    text.push_str(&get_placeholder(&template.language, i));
+    interpolations.push(&code[start..end]);
+    last_end = end;
+  }
+  text.push_str(&code[last_end..usize::from(template.content_range.end())]);
+  (text, interpolations)
+}
+
+fn restore_placeholders(text: String, language: &EmbeddedLanguage, interpolations: &[&str]) -> Option<String> {
+  let mut text = text;
+  for (i, interpolation) in interpolations.iter().enumerate() {
+    let placeholder = get_placeholder(language, i);
+    // bail if the formatter dropped or duplicated a placeholder
+    if text.matches(&placeholder).count() != 1 {
+      return None;
+    }
+    text = text.replacen(&placeholder, interpolation, 1);
+  }
+  Some(text)
+}
+
+fn get_placeholder(language: &EmbeddedLanguage, index: usize) -> String {
+  match language {
+    EmbeddedLanguage::Css | EmbeddedLanguage::Host(_) => format!("{}{}__", PLACEHOLDER_PREFIX, index),
+    EmbeddedLanguage::Graphql => format!("#{}{}__", PLACEHOLDER_PREFIX, index),
+  }
+}
+
+/// Formats the body of a css template.
+///
+/// The body is wrapped in a rule so that it may contain declarations along
+/// with nested rules, which leaves the formatted lines indented one level.
+fn format_css_template(text: &str, config: &Configuration) -> Result<Option<String>> {
+  let wrapped_text = format!("{} {{\n{}\n}}", CSS_WRAPPER_SELECTOR, text);
+  let syntax = CssFileSource::css();
+  let tree = biome_css_parser::parse_css(&wrapped_text, syntax, css_parser_options(config));
+  if tree.has_errors() {
+    return Ok(None);
+  }
+  let options = build_css_options(config, syntax)?;
+  let formatted = biome_css_formatter::format_node(options, &tree.syntax())?
+    .print()?
+    .into_code();
+  let lines = formatted.lines().collect::<Vec<_>>();
+  if lines.len() < 3 || lines[0] != format!("{} {{", CSS_WRAPPER_SELECTOR) || lines[lines.len() - 1] != "}" {
+    return Ok(None);
+  }
+  Ok(Some(lines[1..lines.len() - 1].join("\n")))
+}
+
+fn format_graphql_template(text: &str, config: &Configuration) -> Result<Option<String>> {
+  let tree = biome_graphql_parser::parse_graphql(text);
+  if tree.has_errors() {
+    return Ok(None);
+  }
+  let options = build_graphql_options(config, GraphqlFileSource::default())?;
+  let formatted = biome_graphql_formatter::format_node(options, &tree.syntax())?
+    .print()?
+    .into_code();
+  Ok(Some(formatted))
+}
+
+/// Gets a path for the embedded code that the host can use to choose a plugin (ex. `file.ts.sql`).
+pub fn get_embedded_file_path(file_path: &Path, extension: &str) -> PathBuf {
+  let mut path = file_path.as_os_str().to_owned();
+  path.push(".");
+  path.push(extension);
+  PathBuf::from(path)
+}
+
+pub fn get_indent_unit(config: &Configuration) -> String {
+  match config.javascript_indent_style {
+    Some(IndentStyle::Space) => " ".repeat(config.javascript_indent_width.unwrap_or(2) as usize),
+    Some(IndentStyle::Tab) | None => "\t".to_string(),
+  }
+}
+
+/// Places the formatted content on its own lines between the delimiters of
+/// the block (ex. backticks or tags), with the end delimiter at `end_indent`.
+pub fn indent_block_content(content: &str, content_indent: &str, end_indent: &str, newline: &str) -> String {
+  let mut text = String::with_capacity(content.len() + newline.len() * 2 + end_indent.len());
+  text.push_str(newline);
+  for line in content.lines() {
+    if !line.is_empty() {
+      text.push_str(content_indent);
+      text.push_str(line);
+    }
+    text.push_str(newline);
+  }
+  text.push_str(end_indent);
+  text
+}
+
+/// Removes the indentation common to all non-blank lines, along with any
+/// leading and trailing blank lines.
+pub fn dedent(text: &str) -> String {
+  let lines = text.lines().collect::<Vec<_>>();
+  let Some(first) = lines.iter().position(|line| !line.trim().is_empty()) else {
+    return String::new();
+  };
+  let last = lines.iter().rposition(|line| !line.trim().is_empty()).unwrap();
+  let lines = &lines[first..=last];
+  let min_indent = lines
+    .iter()
+    .filter(|line| !line.trim().is_empty())
+    .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+    .min()
+    .unwrap_or(0);
+  lines
+    .iter()
+    .map(|line| line.get(min_indent..).unwrap_or(""))
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+pub fn get_newline(text: &str) -> &'static str {
+  if text.contains("\r\n") { "\r\n" } else { "\n" }
+}
+
+pub fn get_line_indent(text: &str, pos: usize) -> &str {
+  let line_start = text[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0);
+  let line = &text[line_start..];
+  let indent_len = line.len() - line.trim_start_matches([' ', '\t']).len();
+  &line[..indent_len]
+}
@@ -4,36 +4,130 @@ use biome_css_formatter::context::CssFormatOptions;
 use biome_css_parser::CssModulesKind;
 use biome_css_parser::CssParserOptions;
 use biome_css_syntax::CssFileSource;
+use biome_formatter::AttributePosition;
+use biome_formatter::Expand;
 use biome_formatter::IndentStyle;
 use biome_formatter::LineEnding;
 use biome_formatter::LineWidth;
+use biome_formatter::Printed;
 use biome_formatter::QuoteStyle;
 use biome_graphql_formatter::context::GraphqlFormatOptions;
 use biome_graphql_syntax::GraphqlFileSource;
+use biome_html_formatter::context::HtmlFormatOptions;
+use biome_html_formatter::context::WhitespaceSensitivity;
+use biome_html_parser::HtmlParseOptions;
+use biome_html_syntax::HtmlFileSource;
 use biome_js_formatter::context::ArrowParentheses;
 use biome_js_formatter::context::JsFormatOptions;
+use biome_js_formatter::context::OperatorLinebreak;
 use biome_js_formatter::context::QuoteProperties;
 use biome_js_formatter::context::Semicolons;
 use biome_js_formatter::context::TrailingCommas;
 use biome_js_parser::JsParserOptions;
+use biome_js_syntax::AnyJsExpression;
+use biome_js_syntax::AnyJsLiteralExpression;
+use biome_js_syntax::JsExpressionStatement;
 use biome_js_syntax::JsFileSource;
+use biome_js_syntax::JsSyntaxKind;
+use biome_js_syntax::JsSyntaxNode;
+use biome_js_syntax::JsUnaryExpression;
+use biome_js_syntax::JsUnaryOperator;
+use biome_js_syntax::LanguageVariant;
+use biome_js_syntax::ModuleKind;
 use biome_json_formatter::context::JsonFormatOptions;
+use biome_json_formatter::context::TrailingCommas as JsonTrailingCommas;
 use biome_json_parser::JsonParserOptions;
 use biome_json_parser::ParseDiagnostic;
 use biome_json_parser::parse_json;
+use biome_rowan::AstNode;
+use biome_rowan::TextRange;
+use biome_rowan::TextSize;
 use camino::Utf8Path;
+use std::borrow::Cow;
+use std::ops::Range;
 use std::path::Path;
 use std::str::FromStr;
 
 use crate::configuration::Configuration;
+use crate::embedded::format_embedded_templates;
+use crate::embedded::has_embedded_languages;
+use crate::markup::format_astro;
+use crate::markup::format_html_blocks;
+use crate::markup::format_svelte;
+use crate::markup::format_vue;
+use crate::overrides::get_file_config;
+
+/// Formats code embedded in a file with the host (ex. another dprint plugin),
+/// returning `None` when it's unchanged or nothing handles the file path.
+pub(crate) type HostFormatter<'a> = dyn FnMut(&Path, &str) -> Result<Option<String>> + 'a;
+
+/// File extensions formatted as GraphQL in addition to `graphql.extensions`.
+pub(crate) const GRAPHQL_FILE_EXTENSIONS: [&str; 3] = ["graphql", "gql", "graphqls"];
+
+/// Gets if the lowercase file extension should be formatted as GraphQL.
+pub(crate) fn is_graphql_extension(ext: &str, config: &Configuration) -> bool {
+  GRAPHQL_FILE_EXTENSIONS.contains(&ext) || config.graphql_extensions.iter().any(|e| e == ext)
+}
 
 pub fn format_text(file_path: &Path, input_text: &str, config: &Configuration) -> Result<Option<String>> {
+  format_text_with_host(file_path, input_text, config, |_, _| Ok(None))
+}
+
+/// Formats the text, providing code in embedded languages the plugin can't
+/// format itself (ex. `<style lang="scss">` blocks) to `format_with_host`.
+pub fn format_text_with_host(
+  file_path: &Path,
+  input_text: &str,
+  config: &Configuration,
+  mut format_with_host: impl FnMut(&Path, &str) -> Result<Option<String>>,
+) -> Result<Option<String>> {
+  format_text_inner(file_path, input_text, None, config, &mut format_with_host)
+}
+
+/// Formats only the nodes within the provided byte range, splicing the
+/// formatted code back into the rest of the file.
+pub fn format_range(
+  file_path: &Path,
+  input_text: &str,
+  range: Range<usize>,
+  config: &Configuration,
+) -> Result<Option<String>> {
+  if range.start > range.end || range.end > input_text.len() {
+    bail!(
+      "Invalid range {}..{} for text of length {}.",
+      range.start,
+      range.end,
+      input_text.len()
+    );
+  }
+  let range = TextRange::new(TextSize::try_from(range.start)?, TextSize::try_from(range.end)?);
+  format_text_inner(file_path, input_text, Some(range), config, &mut |_, _| Ok(None))
+}
+
+fn format_text_inner(
+  file_path: &Path,
+  input_text: &str,
+  range: Option<TextRange>,
+  config: &Configuration,
+  format_with_host: &mut HostFormatter,
+) -> Result<Option<String>> {
+  let file_config = get_file_config(file_path, config);
+  let config = &*file_config;
+  #[cfg(not(target_arch = "wasm32"))]
+  let editorconfig_config = crate::editorconfig::apply_editorconfig(file_path, config);
+  #[cfg(not(target_arch = "wasm32"))]
+  let config = editorconfig_config.as_ref();
+  let config = resolve_auto_line_endings(config, input_text);
+  let config = config.as_ref();
   let lower_ext = file_path
     .extension()
     .and_then(|ext| ext.to_str())
     .map(|s| s.to_lowercase());
   let output = match lower_ext.as_deref() {
     Some("json" | "jsonc") => {
+      if config.json_enabled == Some(false) {
+        return Ok(None);
+      }
       let tree = parse_json(
         input_text,
         JsonParserOptions {
@@ -54,32 +148,53 @@ pub fn format_text(file_path: &Path, input_text: &str, config: &Configuration) -
         bail!("{}", get_diagnostics_message(tree.into_diagnostics()));
       }
 
-      let options = build_json_options(config)?;
-      let formatted = biome_json_formatter::format_node(options, &tree.syntax())?;
-      let printed = formatted.print()?;
-      printed.into_code()
+      let options = build_json_options(config, allows_trailing_commas(file_path))?;
+      match range {
+        Some(range) => splice_printed_range(
+          input_text,
+          &biome_json_formatter::format_range(options, &tree.syntax(), range)?,
+        ),
+        None => biome_json_formatter::format_node(options, &tree.syntax())?
+          .print()?
+          .into_code(),
+      }
+    }
+    Some("json5") => {
+      if config.json_enabled == Some(false) || range.is_some() {
+        return Ok(None);
+      }
+      format_json5_text(input_text, config)?
     }
-    Some("js" | "jsx" | "ts" | "tsx" | "cjs" | "mjs" | "cts" | "mts") => {
+    Some(ext @ ("js" | "jsx" | "ts" | "tsx" | "cjs" | "mjs" | "cts" | "mts")) => {
+      let enabled = match ext {
+        "ts" | "tsx" | "cts" | "mts" => config.typescript_enabled,
+        _ => config.javascript_enabled,
+      };
+      if enabled == Some(false) {
+        return Ok(None);
+      }
       let file_path = file_path.to_string_lossy();
       let file_path = Utf8Path::new(&file_path);
-      let Ok(syntax) = JsFileSource::try_from(file_path) else {
+      let Ok(mut syntax) = JsFileSource::try_from(file_path) else {
         return Ok(None);
       };
-
-      let options = build_js_options(config, syntax)?;
-      let tree = biome_js_parser::parse(
+      // .js and .mjs files already allow jsx
+      if config.javascript_jsx_everywhere == Some(true) && ext == "cjs" {
+        syntax = syntax.with_variant(LanguageVariant::Jsx);
+      }
+      match config.javascript_source_type {
+        Some(crate::configuration::SourceType::Module) => syntax = syntax.with_module_kind(ModuleKind::Module),
+        Some(crate::configuration::SourceType::Script) => syntax = syntax.with_module_kind(ModuleKind::Script),
+        Some(crate::configuration::SourceType::Auto) | None => {}
+      }
+      format_js_text(
         input_text,
+        file_path.as_std_path(),
         syntax,
-        JsParserOptions {
-          parse_class_parameter_decorators: true,
-          grit_metavariables: config.javascript_grit_metavariables.unwrap_or(false),
-        },
-      );
-      if tree.has_errors() {
-        bail!("{}", get_diagnostics_message(tree.into_diagnostics()));
-      }
-      let formatted = biome_js_formatter::format_node(options, &tree.syntax(), false)?;
-      formatted.print()?.into_code()
+        range,
+        config,
+        format_with_host,
+      )?
     }
     Some("css") => {
       if config.css_enabled != Some(true) {
@@ -91,46 +206,52 @@ pub fn format_text(file_path: &Path, input_text: &str, config: &Configuration) -
       let Ok(syntax) = CssFileSource::try_from(file_path) else {
         return Ok(None);
       };
-
-      let options = build_css_options(config, syntax)?;
-      let tree = biome_css_parser::parse_css(
-        input_text,
-        syntax,
-        CssParserOptions {
-          allow_wrong_line_comments: true,
-          css_modules: if config.css_css_modules.unwrap_or(false) {
-            CssModulesKind::Classic
-          } else {
-            CssModulesKind::None
-          },
-          grit_metavariables: config.css_grit_metavariables.unwrap_or(false),
-          tailwind_directives: Default::default(),
-        },
-      );
-      if tree.has_errors() {
-        bail!("{}", get_diagnostics_message(tree.into_diagnostics()));
-      }
-      let formatted = biome_css_formatter::format_node(options, &tree.syntax())?;
-      formatted.print()?.into_code()
+      format_css_text(input_text, syntax, range, config)?
     }
-    Some("graphql") => {
+    Some(ext) if is_graphql_extension(ext, config) => {
       if config.graphql_enabled != Some(true) {
         return Ok(None);
       }
 
-      let file_path = file_path.to_string_lossy();
-      let file_path = Utf8Path::new(&file_path);
-      let Ok(syntax) = GraphqlFileSource::try_from(file_path) else {
-        return Ok(None);
-      };
-
-      let options = build_graphql_options(config, syntax)?;
+      let options = build_graphql_options(config, GraphqlFileSource::default())?;
       let tree = biome_graphql_parser::parse_graphql(input_text);
       if tree.has_errors() {
         bail!("{}", get_diagnostics_message(tree.into_diagnostics()));
       }
-      let formatted = biome_graphql_formatter::format_node(options, &tree.syntax())?;
-      formatted.print()?.into_code()
+      match range {
+        Some(range) => splice_printed_range(
+          input_text,
+          &biome_graphql_formatter::format_range(options, &tree.syntax(), range)?,
+        ),
+        None => biome_graphql_formatter::format_node(options, &tree.syntax())?
+          .print()?
+          .into_code(),
+      }
+    }
+    Some("html" | "htm") => {
+      if config.html_enabled != Some(true) || range.is_some() {
+        return Ok(None);
+      }
+      format_html_text(input_text, file_path, config, format_with_host)?
+    }
+    Some("vue") => {
+      // range formatting is not supported for components
+      if config.vue_enabled != Some(true) || range.is_some() {
+        return Ok(None);
+      }
+      format_vue(input_text, file_path, config, format_with_host)?
+    }
+    Some("svelte") => {
+      if config.svelte_enabled != Some(true) || range.is_some() {
+        return Ok(None);
+      }
+      format_svelte(input_text, file_path, config, format_with_host)?
+    }
+    Some("astro") => {
+      if config.astro_enabled != Some(true) || range.is_some() {
+        return Ok(None);
+      }
+      format_astro(input_text, file_path, config, format_with_host)?
     }
     _ => return Ok(None),
   };
@@ -141,7 +262,271 @@ pub fn format_text(file_path: &Path, input_text: &str, config: &Configuration) -
   }
 }
 
-fn get_diagnostics_message(diagnostics: Vec<ParseDiagnostic>) -> String {
+pub(crate) fn format_js_text(
+  input_text: &str,
+  file_path: &Path,
+  syntax: JsFileSource,
+  range: Option<TextRange>,
+  config: &Configuration,
+  format_with_host: &mut HostFormatter,
+) -> Result<String> {
+  let options = build_js_options(config, syntax)?;
+  let tree = biome_js_parser::parse(input_text, syntax, js_parser_options(config));
+  if tree.has_errors() {
+    bail!("{}", get_diagnostics_message(tree.into_diagnostics()));
+  }
+  Ok(match range {
+    Some(range) => splice_printed_range(
+      input_text,
+      &biome_js_formatter::format_range(options, &tree.syntax(), range)?,
+    ),
+    None => {
+      let output = biome_js_formatter::format_node(options, &tree.syntax(), false)?
+        .print()?
+        .into_code();
+      if has_embedded_languages(config) {
+        format_embedded_templates(&output, file_path, syntax, config, format_with_host)?
+      } else {
+        output
+      }
+    }
+  })
+}
+
+pub(crate) fn format_css_text(
+  input_text: &str,
+  syntax: CssFileSource,
+  range: Option<TextRange>,
+  config: &Configuration,
+) -> Result<String> {
+  let options = build_css_options(config, syntax)?;
+  let tree = biome_css_parser::parse_css(input_text, syntax, css_parser_options(config));
+  if tree.has_errors() {
+    bail!("{}", get_diagnostics_message(tree.into_diagnostics()));
+  }
+  Ok(match range {
+    Some(range) => splice_printed_range(
+      input_text,
+      &biome_css_formatter::format_range(options, &tree.syntax(), range)?,
+    ),
+    None => biome_css_formatter::format_node(options, &tree.syntax())?
+      .print()?
+      .into_code(),
+  })
+}
+
+/// Formats JSON5, which Biome's JSON parser doesn't support, as a JavaScript
+/// expression since JSON5 is a subset of ES5's expression syntax.
+fn format_json5_text(input_text: &str, config: &Configuration) -> Result<String> {
+  let trimmed_text = input_text.trim();
+  if trimmed_text.is_empty() {
+    return Ok(trimmed_text.to_string());
+  }
+
+  // wrap the value in parens so that it's parsed as a single expression, putting
+  // the closing paren on a new line in case the value ends with a line comment
+  let value_start = get_leading_comments_end(input_text);
+  let code = format!("{}({}\n)", &input_text[..value_start], &input_text[value_start..]);
+  let syntax = JsFileSource::js_module();
+  let tree = biome_js_parser::parse(&code, syntax, JsParserOptions::default());
+  if tree.has_errors() {
+    bail!("{}", get_diagnostics_message(tree.into_diagnostics()));
+  }
+  // the javascript parser accepts any expression (ex. `foo()`), so check the value is json5
+  if let Some(node) = tree.syntax().descendants().find(|node| !is_json5_node(node)) {
+    bail!("Expected a JSON5 value, but found: {}", node.text_trimmed());
+  }
+  let options = build_json5_options(config)?;
+  let output = biome_js_formatter::format_node(options, &tree.syntax(), false)?
+    .print()?
+    .into_code();
+
+  // now remove the parens (if the formatter kept them) and the statement's semicolon
+  let tree = biome_js_parser::parse(&output, syntax, JsParserOptions::default());
+  let Some(statement) = tree.syntax().descendants().find_map(JsExpressionStatement::cast) else {
+    bail!("Expected a JSON5 value.");
+  };
+  let mut removed_ranges = Vec::with_capacity(3);
+  if let Ok(AnyJsExpression::JsParenthesizedExpression(expr)) = statement.expression() {
+    if let (Ok(l_paren), Ok(r_paren)) = (expr.l_paren_token(), expr.r_paren_token()) {
+      removed_ranges.push(l_paren.text_trimmed_range());
+      removed_ranges.push(r_paren.text_trimmed_range());
+    }
+  }
+  if let Some(semicolon) = statement.semicolon_token() {
+    removed_ranges.push(semicolon.text_trimmed_range());
+  }
+  let mut text = String::with_capacity(output.len());
+  let mut last_end = 0;
+  for range in removed_ranges {
+    text.push_str(&output[last_end..usize::from(range.start())]);
+    last_end = usize::from(range.end());
+  }
+  text.push_str(&output[last_end..]);
+  Ok(text)
+}
+
+/// Gets if the node of the parsed expression is allowed in a JSON5 value,
+/// which only has objects, arrays, literals and signed numbers.
+fn is_json5_node(node: &JsSyntaxNode) -> bool {
+  match node.kind() {
+    JsSyntaxKind::JS_MODULE
+    | JsSyntaxKind::JS_DIRECTIVE_LIST
+    | JsSyntaxKind::JS_EXPRESSION_STATEMENT
+    | JsSyntaxKind::JS_OBJECT_EXPRESSION
+    | JsSyntaxKind::JS_OBJECT_MEMBER_LIST
+    | JsSyntaxKind::JS_PROPERTY_OBJECT_MEMBER
+    | JsSyntaxKind::JS_LITERAL_MEMBER_NAME
+    | JsSyntaxKind::JS_ARRAY_EXPRESSION
+    | JsSyntaxKind::JS_ARRAY_ELEMENT_LIST
+    | JsSyntaxKind::JS_STRING_LITERAL_EXPRESSION
+    | JsSyntaxKind::JS_NUMBER_LITERAL_EXPRESSION
+    | JsSyntaxKind::JS_BOOLEAN_LITERAL_EXPRESSION
+    | JsSyntaxKind::JS_NULL_LITERAL_EXPRESSION => true,
+    // a single statement with the value
+    JsSyntaxKind::JS_MODULE_ITEM_LIST => node.children().count() == 1,
+    // only the parens wrapping the value
+    JsSyntaxKind::JS_PARENTHESIZED_EXPRESSION => node
+      .parent()
+      .is_some_and(|parent| parent.kind() == JsSyntaxKind::JS_EXPRESSION_STATEMENT),
+    JsSyntaxKind::JS_UNARY_EXPRESSION => JsUnaryExpression::cast_ref(node).is_some_and(|expr| {
+      matches!(expr.operator(), Ok(JsUnaryOperator::Plus | JsUnaryOperator::Minus))
+        && matches!(
+          expr.argument(),
+          Ok(
+            AnyJsExpression::AnyJsLiteralExpression(AnyJsLiteralExpression::JsNumberLiteralExpression(_))
+              | AnyJsExpression::JsIdentifierExpression(_)
+          )
+        )
+    }),
+    JsSyntaxKind::JS_IDENTIFIER_EXPRESSION | JsSyntaxKind::JS_REFERENCE_IDENTIFIER => {
+      matches!(node.text_trimmed().to_string().as_str(), "Infinity" | "NaN")
+    }
+    _ => false,
+  }
+}
+
+/// Gets the position after any leading whitespace and comments.
+fn get_leading_comments_end(text: &str) -> usize {
+  let mut pos = 0;
+  loop {
+    let rest = &text[pos..];
+    let trimmed = rest.trim_start();
+    pos += rest.len() - trimmed.len();
+    if trimmed.starts_with("//") {
+      match trimmed.find('\n') {
+        Some(index) => pos += index + 1,
+        None => return text.len(),
+      }
+    } else if let Some(comment_text) = trimmed.strip_prefix("/*") {
+      match comment_text.find("*/") {
+        Some(index) => pos += 2 + index + 2,
+        None => return text.len(),
+      }
+    } else {
+      return pos;
+    }
+  }
+}
+
+fn format_html_text(
+  input_text: &str,
+  file_path: &Path,
+  config: &Configuration,
+  format_with_host: &mut HostFormatter,
+) -> Result<String> {
+  let syntax = HtmlFileSource::html();
+  let options = build_html_options(config, syntax)?;
+  let tree = biome_html_parser::parse_html(input_text, HtmlParseOptions::from(&syntax));
+  if tree.has_errors() {
+    bail!("{}", get_diagnostics_message(tree.into_diagnostics()));
+  }
+  let output = biome_html_formatter::format_node(options, &tree.syntax(), false)?
+    .print()?
+    .into_code();
+  // the content of script and style elements is printed as-is
+  format_html_blocks(&output, file_path, config, format_with_host)
+}
+
+/// Resolves `lineEnding: auto` to the line ending used most in the text.
+fn resolve_auto_line_endings<'a>(config: &'a Configuration, text: &str) -> Cow<'a, Configuration> {
+  let line_endings = [
+    config.line_ending,
+    config.css_line_ending,
+    config.graphql_line_ending,
+    config.javascript_line_ending,
+    config.json_line_ending,
+  ];
+  if !line_endings.contains(&Some(crate::configuration::LineEnding::Auto)) {
+    return Cow::Borrowed(config);
+  }
+
+  let line_ending = Some(get_dominant_line_ending(text));
+  let resolve = |value: Option<crate::configuration::LineEnding>| match value {
+    Some(crate::configuration::LineEnding::Auto) => line_ending,
+    value => value,
+  };
+  Cow::Owned(Configuration {
+    line_ending: resolve(config.line_ending),
+    css_line_ending: resolve(config.css_line_ending),
+    graphql_line_ending: resolve(config.graphql_line_ending),
+    javascript_line_ending: resolve(config.javascript_line_ending),
+    json_line_ending: resolve(config.json_line_ending),
+    ..config.clone()
+  })
+}
+
+/// Gets the line ending used most in the text, which is `lf` when it has no newlines.
+fn get_dominant_line_ending(text: &str) -> crate::configuration::LineEnding {
+  let bytes = text.as_bytes();
+  let mut lf_count = 0;
+  let mut crlf_count = 0;
+  let mut cr_count = 0;
+  for (i, byte) in bytes.iter().enumerate() {
+    match byte {
+      b'\n' if i > 0 && bytes[i - 1] == b'\r' => crlf_count += 1,
+      b'\n' => lf_count += 1,
+      b'\r' if bytes.get(i + 1) != Some(&b'\n') => cr_count += 1,
+      _ => {}
+    }
+  }
+  if crlf_count > lf_count && crlf_count >= cr_count {
+    crate::configuration::LineEnding::Crlf
+  } else if cr_count > lf_count && cr_count > crlf_count {
+    crate::configuration::LineEnding::Cr
+  } else {
+    crate::configuration::LineEnding::Lf
+  }
+}
+
+fn get_line_ending(line_ending: crate::configuration::LineEnding) -> LineEnding {
+  match line_ending {
+    crate::configuration::LineEnding::Lf => LineEnding::Lf,
+    crate::configuration::LineEnding::Cr => LineEnding::Cr,
+    crate::configuration::LineEnding::Crlf => LineEnding::Crlf,
+    // resolved from the text in format_text
+    crate::configuration::LineEnding::Auto => LineEnding::Lf,
+  }
+}
+
+/// Replaces the source range covered by a range format with its printed code.
+fn splice_printed_range(input_text: &str, printed: &Printed) -> String {
+  match printed.range() {
+    Some(range) => {
+      let start = usize::from(range.start());
+      let end = usize::from(range.end());
+      let mut text = String::with_capacity(input_text.len() - (end - start) + printed.as_code().len());
+      text.push_str(&input_text[..start]);
+      text.push_str(printed.as_code());
+      text.push_str(&input_text[end..]);
+      text
+    }
+    // nothing in the range was formatted
+    None => input_text.to_string(),
+  }
+}
+
+pub(crate) fn get_diagnostics_message(diagnostics: Vec<ParseDiagnostic>) -> String {
   let mut text = String::new();
   for (i, diagnostic) in diagnostics.into_iter().enumerate() {
     if i > 0 {
@@ -154,7 +539,50 @@ fn get_diagnostics_message(diagnostics: Vec<ParseDiagnostic>) -> String {
   text
 }
 
-fn build_json_options(config: &Configuration) -> Result<JsonFormatOptions> {
+pub(crate) fn js_parser_options(config: &Configuration) -> JsParserOptions {
+  JsParserOptions {
+    parse_class_parameter_decorators: config
+      .javascript_parser_unsafe_parameter_decorators_enabled
+      .unwrap_or(true),
+    grit_metavariables: config.javascript_grit_metavariables.unwrap_or(false),
+  }
+}
+
+pub(crate) fn css_parser_options(config: &Configuration) -> CssParserOptions {
+  CssParserOptions {
+    allow_wrong_line_comments: true,
+    css_modules: if config.css_css_modules.unwrap_or(false) {
+      CssModulesKind::Classic
+    } else {
+      CssModulesKind::None
+    },
+    grit_metavariables: config.css_grit_metavariables.unwrap_or(false),
+    tailwind_directives: config.css_tailwind_directives.unwrap_or(false),
+  }
+}
+
+/// Gets if the JSON file is a .jsonc file or one of the well known JSON files
+/// that are parsed as JSON with comments and allow trailing commas.
+fn allows_trailing_commas(file_path: &Path) -> bool {
+  let to_lowercase =
+    |value: Option<&std::ffi::OsStr>| value.and_then(|value| value.to_str()).map(|value| value.to_lowercase());
+  if to_lowercase(file_path.extension()).as_deref() == Some("jsonc") {
+    return true;
+  }
+  let Some(file_name) = to_lowercase(file_path.file_name()) else {
+    return false;
+  };
+  let in_vscode_dir =
+    to_lowercase(file_path.parent().and_then(|parent| parent.file_name())).as_deref() == Some(".vscode");
+  in_vscode_dir
+    || file_name == "devcontainer.json"
+    || file_name == ".devcontainer.json"
+    || ["tsconfig.", "jsconfig."]
+      .iter()
+      .any(|prefix| file_name.starts_with(prefix) && file_name.ends_with(".json"))
+}
+
+fn build_json_options(config: &Configuration, allow_trailing_commas: bool) -> Result<JsonFormatOptions> {
   let mut options = JsonFormatOptions::default();
   if let Some(indent_style) = config.json_indent_style {
     options = options.with_indent_style(match indent_style {
@@ -167,23 +595,39 @@ fn build_json_options(config: &Configuration) -> Result<JsonFormatOptions> {
       options = options.with_indent_width(value);
     }
   }
-  if let Some(line_ending) = config.line_ending {
-    options = options.with_line_ending(match line_ending {
-      crate::configuration::LineEnding::Lf => LineEnding::Lf,
-      crate::configuration::LineEnding::Cr => LineEnding::Cr,
-      crate::configuration::LineEnding::Crlf => LineEnding::Crlf,
-    });
+  if let Some(line_ending) = config.json_line_ending.or(config.line_ending) {
+    options = options.with_line_ending(get_line_ending(line_ending));
   }
   if let Some(line_width) = config.json_line_width {
     options = options.with_line_width(
       LineWidth::from_str(&line_width.to_string()).map_err(|err| anyhow::anyhow!("{} (Value: {})", err, line_width))?,
     );
   }
+  if let Some(expand) = config.json_expand {
+    options = options.with_expand(match expand {
+      crate::configuration::Expand::Auto => Expand::Auto,
+      crate::configuration::Expand::Always => Expand::Always,
+      crate::configuration::Expand::Never => Expand::Never,
+    });
+  }
+  if let Some(trailing_commas) = config.json_trailing_commas {
+    options = options.with_trailing_commas(match trailing_commas {
+      crate::configuration::JsonTrailingCommas::All if allow_trailing_commas => JsonTrailingCommas::All,
+      // trailing commas are invalid in other json files
+      _ => JsonTrailingCommas::None,
+    });
+  }
+  if let Some(bracket_spacing) = config.json_bracket_spacing {
+    options = options.with_bracket_spacing(bracket_spacing.into());
+  }
   Ok(options)
 }
 
-fn build_css_options(config: &Configuration, syntax: CssFileSource) -> Result<CssFormatOptions> {
+pub(crate) fn build_css_options(config: &Configuration, syntax: CssFileSource) -> Result<CssFormatOptions> {
   let mut options = CssFormatOptions::new(syntax);
+  if let Some(line_ending) = config.css_line_ending.or(config.line_ending) {
+    options = options.with_line_ending(get_line_ending(line_ending));
+  }
   if let Some(indent_style) = config.css_indent_style {
     options = options.with_indent_style(match indent_style {
       crate::configuration::IndentStyle::Tab => IndentStyle::Tab,
@@ -209,8 +653,11 @@ fn build_css_options(config: &Configuration, syntax: CssFileSource) -> Result<Cs
   Ok(options)
 }
 
-fn build_graphql_options(config: &Configuration, syntax: GraphqlFileSource) -> Result<GraphqlFormatOptions> {
+pub(crate) fn build_graphql_options(config: &Configuration, syntax: GraphqlFileSource) -> Result<GraphqlFormatOptions> {
   let mut options = GraphqlFormatOptions::new(syntax);
+  if let Some(line_ending) = config.graphql_line_ending.or(config.line_ending) {
+    options = options.with_line_ending(get_line_ending(line_ending));
+  }
   if let Some(indent_style) = config.graphql_indent_style {
     options = options.with_indent_style(match indent_style {
       crate::configuration::IndentStyle::Tab => IndentStyle::Tab,
@@ -239,15 +686,97 @@ fn build_graphql_options(config: &Configuration, syntax: GraphqlFileSource) -> R
   Ok(options)
 }
 
-fn build_js_options(config: &Configuration, syntax: JsFileSource) -> Result<JsFormatOptions> {
-  let mut options = JsFormatOptions::new(syntax);
+fn build_html_options(config: &Configuration, syntax: HtmlFileSource) -> Result<HtmlFormatOptions> {
+  let mut options = HtmlFormatOptions::new(syntax);
   if let Some(line_ending) = config.line_ending {
-    options = options.with_line_ending(match line_ending {
-      crate::configuration::LineEnding::Crlf => LineEnding::Crlf,
-      crate::configuration::LineEnding::Lf => LineEnding::Lf,
-      crate::configuration::LineEnding::Cr => LineEnding::Cr,
+    options = options.with_line_ending(get_line_ending(line_ending));
+  }
+  if let Some(indent_style) = config.html_indent_style {
+    options = options.with_indent_style(match indent_style {
+      crate::configuration::IndentStyle::Tab => IndentStyle::Tab,
+      crate::configuration::IndentStyle::Space => IndentStyle::Space,
     });
   }
+  if let Some(value) = config.html_indent_width {
+    if let Ok(value) = value.try_into() {
+      options = options.with_indent_width(value);
+    }
+  }
+  if let Some(line_width) = config.html_line_width {
+    options = options.with_line_width(
+      LineWidth::from_str(&line_width.to_string()).map_err(|err| anyhow::anyhow!("{} (Value: {})", err, line_width))?,
+    );
+  }
+  if let Some(attribute_position) = config.html_attribute_position {
+    options = options.with_attribute_position(match attribute_position {
+      crate::configuration::AttributePosition::Auto => AttributePosition::Auto,
+      crate::configuration::AttributePosition::Multiline => AttributePosition::Multiline,
+    });
+  }
+  if let Some(bracket_same_line) = config.html_bracket_same_line {
+    options = options.with_bracket_same_line(bracket_same_line.into());
+  }
+  if let Some(whitespace_sensitivity) = config.html_whitespace_sensitivity {
+    options = options.with_whitespace_sensitivity(match whitespace_sensitivity {
+      crate::configuration::WhitespaceSensitivity::Css => WhitespaceSensitivity::Css,
+      crate::configuration::WhitespaceSensitivity::Strict => WhitespaceSensitivity::Strict,
+      crate::configuration::WhitespaceSensitivity::Ignore => WhitespaceSensitivity::Ignore,
+    });
+  }
+  Ok(options)
+}
+
+fn build_json5_options(config: &Configuration) -> Result<JsFormatOptions> {
+  let mut options = JsFormatOptions::new(JsFileSource::js_module())
+    .with_semicolons(Semicolons::Always)
+    .with_trailing_commas(TrailingCommas::None)
+    .with_quote_properties(QuoteProperties::Preserve);
+  if let Some(line_ending) = config.json_line_ending.or(config.line_ending) {
+    options = options.with_line_ending(get_line_ending(line_ending));
+  }
+  if let Some(indent_style) = config.json_indent_style {
+    options = options.with_indent_style(match indent_style {
+      crate::configuration::IndentStyle::Tab => IndentStyle::Tab,
+      crate::configuration::IndentStyle::Space => IndentStyle::Space,
+    });
+  }
+  if let Some(value) = config.json_indent_width {
+    if let Ok(value) = value.try_into() {
+      options = options.with_indent_width(value);
+    }
+  }
+  if let Some(line_width) = config.json_line_width {
+    options = options.with_line_width(
+      LineWidth::from_str(&line_width.to_string()).map_err(|err| anyhow::anyhow!("{} (Value: {})", err, line_width))?,
+    );
+  }
+  if let Some(quote_style) = &config.json5_quote_style {
+    options = options.with_quote_style(match quote_style {
+      crate::configuration::QuoteStyle::Single => QuoteStyle::Single,
+      crate::configuration::QuoteStyle::Double => QuoteStyle::Double,
+    })
+  }
+  if let Some(quote_properties) = &config.json5_quote_properties {
+    options = options.with_quote_properties(match quote_properties {
+      crate::configuration::QuoteProperties::AsNeeded => QuoteProperties::AsNeeded,
+      crate::configuration::QuoteProperties::Preserve => QuoteProperties::Preserve,
+    })
+  }
+  if let Some(expand) = config.json_expand {
+    options = options.with_expand(match expand {
+      crate::configuration::Expand::Auto => Expand::Auto,
+      crate::configuration::Expand::Always => Expand::Always,
+      crate::configuration::Expand::Never => Expand::Never,
+    });
+  }
+  Ok(options)
+}
+
+fn build_js_options(config: &Configuration, syntax: JsFileSource) -> Result<JsFormatOptions> {
+  let mut options = JsFormatOptions::new(syntax);
+  if let Some(line_ending) = config.javascript_line_ending.or(config.line_ending) {
+    options = options.with_line_ending(get_line_ending(line_ending));
+  }
   if let Some(indent_style) = config.javascript_indent_style {
     options = options.with_indent_style(match indent_style {
       crate::configuration::IndentStyle::Tab => IndentStyle::Tab,
@@ -316,6 +845,28 @@ fn build_js_options(config: &Configuration, syntax: JsFileSource) -> Result<JsFo
     options = options.with_bracket_same_line((*bracket_same_line).into());
   }
 
+  if let Some(expand) = config.javascript_expand {
+    options = options.with_expand(match expand {
+      crate::configuration::Expand::Auto => Expand::Auto,
+      crate::configuration::Expand::Always => Expand::Always,
+      crate::configuration::Expand::Never => Expand::Never,
+    });
+  }
+
+  if let Some(attribute_position) = config.javascript_attribute_position {
+    options = options.with_attribute_position(match attribute_position {
+      crate::configuration::AttributePosition::Auto => AttributePosition::Auto,
+      crate::configuration::AttributePosition::Multiline => AttributePosition::Multiline,
+    });
+  }
+
+  if let Some(operator_linebreak) = config.javascript_operator_linebreak {
+    options = options.with_operator_linebreak(match operator_linebreak {
+      crate::configuration::OperatorLinebreak::After => OperatorLinebreak::After,
+      crate::configuration::OperatorLinebreak::Before => OperatorLinebreak::Before,
+    });
+  }
+
   Ok(options)
 }
 
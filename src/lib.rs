@@ -1,7 +1,14 @@
 pub mod configuration;
+#[cfg(not(target_arch = "wasm32"))]
+mod editorconfig;
+mod embedded;
 mod format_text;
+mod markup;
+mod overrides;
 
+pub use format_text::format_range;
 pub use format_text::format_text;
+pub use format_text::format_text_with_host;
 
 #[cfg(feature = "wasm")]
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
@@ -0,0 +1,502 @@
+use anyhow::Result;
+use biome_css_syntax::CssFileSource;
+use biome_js_syntax::JsFileSource;
+use biome_js_syntax::ModuleKind;
+use std::ops::Range;
+use std::path::Path;
+
+use crate::configuration::Configuration;
+use crate::configuration::IndentStyle;
+use crate::embedded::dedent;
+use crate::embedded::get_embedded_file_path;
+use crate::embedded::get_line_indent;
+use crate::embedded::get_newline;
+use crate::embedded::indent_block_content;
+use crate::format_text::HostFormatter;
+use crate::format_text::format_css_text;
+use crate::format_text::format_js_text;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum MarkupBlockKind {
+  Script,
+  Style,
+}
+
+impl MarkupBlockKind {
+  fn tag_name(&self) -> &'static str {
+    match self {
+      MarkupBlockKind::Script => "script",
+      MarkupBlockKind::Style => "style",
+    }
+  }
+}
+
+/// The indentation of the content of the blocks, which is also used when
+/// formatting the content so that every line is indented the same way.
+#[derive(Clone, Copy)]
+pub struct BlockIndent {
+  pub style: IndentStyle,
+  pub width: u8,
+}
+
+impl BlockIndent {
+  fn html(config: &Configuration) -> Self {
+    BlockIndent {
+      style: config.html_indent_style.unwrap_or(IndentStyle::Tab),
+      width: config.html_indent_width.unwrap_or(2),
+    }
+  }
+
+  fn javascript(config: &Configuration) -> Self {
+    BlockIndent {
+      style: config.javascript_indent_style.unwrap_or(IndentStyle::Tab),
+      width: config.javascript_indent_width.unwrap_or(2),
+    }
+  }
+
+  /// Gets the indentation of the content, which is one level more than the
+  /// line of the opening tag with that line's indentation converted to the
+  /// configured style so spaces and tabs aren't mixed.
+  fn get_content_indent(&self, block: &MarkupBlock) -> String {
+    let width = std::cmp::max(self.width, 1) as usize;
+    let columns = block
+      .indent
+      .chars()
+      .map(|c| if c == '\t' { width } else { 1 })
+      .sum::<usize>();
+    match self.style {
+      IndentStyle::Space => " ".repeat(columns + width),
+      IndentStyle::Tab => "\t".repeat(columns.div_ceil(width) + 1),
+    }
+  }
+
+  /// Gets the configuration for formatting the content with this indentation.
+  fn get_block_config(&self, config: &Configuration) -> Configuration {
+    Configuration {
+      css_indent_style: Some(self.style),
+      css_indent_width: Some(self.width),
+      javascript_indent_style: Some(self.style),
+      javascript_indent_width: Some(self.width),
+      ..config.clone()
+    }
+  }
+}
+
+/// A `<script>` or `<style>` element within a markup based file.
+pub struct MarkupBlock<'a> {
+  pub kind: MarkupBlockKind,
+  /// Raw text of the attributes in the opening tag.
+  pub attributes: &'a str,
+  /// Indentation of the line the opening tag is on.
+  pub indent: &'a str,
+  /// Range of the text between the opening and closing tags.
+  pub content_range: Range<usize>,
+}
+
+/// Formats the `<script>` and `<style>` elements of an already formatted HTML file,
+/// indenting their content with the HTML indentation options.
+///
+/// Scripts are classic scripts unless they have `type="module"`.
+pub fn format_html_blocks(
+  text: &str,
+  file_path: &Path,
+  config: &Configuration,
+  format_with_host: &mut HostFormatter,
+) -> Result<String> {
+  let blocks = find_blocks(text, 0, None);
+  format_blocks(
+    text,
+    file_path,
+    &blocks,
+    JsFileSource::js_script(),
+    Some(BlockIndent::html(config)),
+    config,
+    format_with_host,
+  )
+}
+
+/// Formats the `<script>` and `<style>` blocks of a Vue single file component.
+pub fn format_vue(
+  text: &str,
+  file_path: &Path,
+  config: &Configuration,
+  format_with_host: &mut HostFormatter,
+) -> Result<String> {
+  let config = &get_component_config(config);
+  let blocks = find_blocks(text, 0, Some("template"));
+  format_blocks(
+    text,
+    file_path,
+    &blocks,
+    JsFileSource::js_module(),
+    config
+      .vue_indent_script_and_style
+      .unwrap_or(false)
+      .then(|| BlockIndent::javascript(config)),
+    config,
+    format_with_host,
+  )
+}
+
+/// Formats the `<script>` and `<style>` blocks of a Svelte component.
+pub fn format_svelte(
+  text: &str,
+  file_path: &Path,
+  config: &Configuration,
+  format_with_host: &mut HostFormatter,
+) -> Result<String> {
+  let config = &get_component_config(config);
+  let blocks = find_blocks(text, 0, None);
+  format_blocks(
+    text,
+    file_path,
+    &blocks,
+    JsFileSource::js_module(),
+    config
+      .svelte_indent_script_and_style
+      .unwrap_or(true)
+      .then(|| BlockIndent::javascript(config)),
+    config,
+    format_with_host,
+  )
+}
+
+/// Formats the frontmatter, `<script>` and `<style>` blocks of an Astro component.
+pub fn format_astro(
+  text: &str,
+  file_path: &Path,
+  config: &Configuration,
+  format_with_host: &mut HostFormatter,
+) -> Result<String> {
+  let config = &get_component_config(config);
+  let (text, markup_start) = match get_frontmatter_range(text) {
+    Some(range) => {
+      let content = &text[range.clone()];
+      let mut new_text = String::with_capacity(text.len());
+      new_text.push_str(&text[..range.start]);
+      if content.trim().is_empty() {
+        new_text.push_str(content);
+      } else {
+        let formatted = format_js_text(
+          &dedent(content),
+          file_path,
+          JsFileSource::astro(),
+          None,
+          config,
+          format_with_host,
+        )?;
+        new_text.push_str(&indent_block_content(&formatted, "", "", get_newline(text)));
+      }
+      let markup_start = new_text.len();
+      new_text.push_str(&text[range.end..]);
+      (new_text, markup_start)
+    }
+    None => (text.to_string(), 0),
+  };
+  // scripts in astro components are processed as typescript
+  let blocks = find_blocks(&text, markup_start, None);
+  format_blocks(
+    &text,
+    file_path,
+    &blocks,
+    JsFileSource::ts(),
+    config
+      .astro_indent_script_and_style
+      .unwrap_or(true)
+      .then(|| BlockIndent::javascript(config)),
+    config,
+    format_with_host,
+  )
+}
+
+/// Gets the configuration for a component, where `<style>` blocks are parsed
+/// as CSS modules so that scoped styles can use `:global(...)`.
+fn get_component_config(config: &Configuration) -> Configuration {
+  Configuration {
+    css_css_modules: Some(true),
+    ..config.clone()
+  }
+}
+
+/// Gets the range of the code between the `---` fences at the start of an Astro component.
+fn get_frontmatter_range(text: &str) -> Option<Range<usize>> {
+  let start = text.len() - text.trim_start().len();
+  if !text[start..].starts_with("---") {
+    return None;
+  }
+  let content_start = start + 3;
+  let first_line_end = content_start + text[content_start..].find('\n')?;
+  if !text[content_start..first_line_end].trim().is_empty() {
+    return None;
+  }
+  let mut line_start = first_line_end + 1;
+  while line_start < text.len() {
+    let line_end = text[line_start..]
+      .find('\n')
+      .map(|i| line_start + i)
+      .unwrap_or(text.len());
+    if text[line_start..line_end].trim_end() == "---" {
+      return Some(content_start..line_start);
+    }
+    line_start = line_end + 1;
+  }
+  None
+}
+
+/// Formats the content of the provided blocks, leaving the rest of the text as-is.
+///
+/// The content is indented one level from the opening tag and formatted with
+/// the indentation of `block_indent` when provided.
+pub fn format_blocks(
+  text: &str,
+  file_path: &Path,
+  blocks: &[MarkupBlock],
+  default_script_syntax: JsFileSource,
+  block_indent: Option<BlockIndent>,
+  config: &Configuration,
+  format_with_host: &mut HostFormatter,
+) -> Result<String> {
+  let newline = get_newline(text);
+  let block_config = block_indent.map(|block_indent| block_indent.get_block_config(config));
+  let config = block_config.as_ref().unwrap_or(config);
+  let mut result = String::with_capacity(text.len());
+  let mut last_end = 0;
+  for block in blocks {
+    let content = &text[block.content_range.clone()];
+    if content.trim().is_empty() {
+      continue;
+    }
+    let Some(formatted) = format_block(
+      block,
+      &dedent(content),
+      file_path,
+      default_script_syntax,
+      config,
+      format_with_host,
+    )?
+    else {
+      continue;
+    };
+    let content_indent = match block_indent {
+      Some(block_indent) => block_indent.get_content_indent(block),
+      None => block.indent.to_string(),
+    };
+    result.push_str(&text[last_end..block.content_range.start]);
+    result.push_str(&indent_block_content(
+      &formatted,
+      &content_indent,
+      block.indent,
+      newline,
+    ));
+    last_end = block.content_range.end;
+  }
+  result.push_str(&text[last_end..]);
+  Ok(result)
+}
+
+fn format_block(
+  block: &MarkupBlock,
+  content: &str,
+  file_path: &Path,
+  default_script_syntax: JsFileSource,
+  config: &Configuration,
+  format_with_host: &mut HostFormatter,
+) -> Result<Option<String>> {
+  let lang = get_attribute(block.attributes, "lang");
+  match (block.kind, lang) {
+    (MarkupBlockKind::Script, _) if !is_javascript_type(get_attribute(block.attributes, "type")) => Ok(None),
+    (MarkupBlockKind::Script, None) => {
+      let syntax = match get_attribute(block.attributes, "type") {
+        Some("module") => default_script_syntax.with_module_kind(ModuleKind::Module),
+        _ => default_script_syntax,
+      };
+      format_js_text(content, file_path, syntax, None, config, format_with_host).map(Some)
+    }
+    (MarkupBlockKind::Script, Some(lang)) => match get_script_file_source(lang) {
+      Some(syntax) => format_js_text(content, file_path, syntax, None, config, format_with_host).map(Some),
+      None => format_block_with_host(lang, content, file_path, config, format_with_host),
+    },
+    (MarkupBlockKind::Style, None | Some("css")) => {
+      format_css_text(content, CssFileSource::css(), None, config).map(Some)
+    }
+    (MarkupBlockKind::Style, Some(lang)) => format_block_with_host(lang, content, file_path, config, format_with_host),
+  }
+}
+
+/// Formats the block with the host, using the extension configured for the
+/// language in `embeddedLanguages` or otherwise the language itself (ex. `scss`).
+fn format_block_with_host(
+  lang: &str,
+  content: &str,
+  file_path: &Path,
+  config: &Configuration,
+  format_with_host: &mut HostFormatter,
+) -> Result<Option<String>> {
+  let extension = config
+    .embedded_languages
+    .get(lang)
+    .map(|ext| ext.as_str())
+    .unwrap_or(lang);
+  format_with_host(&get_embedded_file_path(file_path, extension), content)
+}
+
+fn is_javascript_type(script_type: Option<&str>) -> bool {
+  matches!(
+    script_type,
+    None | Some("module" | "text/javascript" | "application/javascript")
+  )
+}
+
+fn get_script_file_source(lang: &str) -> Option<JsFileSource> {
+  match lang {
+    "js" | "javascript" => Some(JsFileSource::js_module()),
+    "jsx" => Some(JsFileSource::jsx()),
+    "ts" | "typescript" => Some(JsFileSource::ts()),
+    "tsx" => Some(JsFileSource::tsx()),
+    _ => None,
+  }
+}
+
+/// Finds the `<script>` and `<style>` elements in the text after `start`, ignoring
+/// any found within comments or within the provided element (ex. Vue's `<template>`).
+pub fn find_blocks<'a>(text: &'a str, start: usize, skipped_element: Option<&str>) -> Vec<MarkupBlock<'a>> {
+  let mut blocks = Vec::new();
+  let mut skipped_depth = 0;
+  let mut pos = start;
+  while let Some(offset) = text[pos..].find('<') {
+    let start = pos + offset;
+    let rest = &text[start + 1..];
+    if rest.starts_with("!--") {
+      match rest.find("-->") {
+        Some(end) => {
+          pos = start + 1 + end + 3;
+          continue;
+        }
+        None => break,
+      }
+    }
+    if let Some(name) = skipped_element {
+      if starts_with_tag_name(rest, name) {
+        skipped_depth += 1;
+      } else if rest.starts_with('/') && starts_with_tag_name(&rest[1..], name) {
+        skipped_depth = std::cmp::max(skipped_depth, 1) - 1;
+      }
+    }
+    let kind = if starts_with_tag_name(rest, "script") {
+      MarkupBlockKind::Script
+    } else if starts_with_tag_name(rest, "style") {
+      MarkupBlockKind::Style
+    } else {
+      pos = start + 1;
+      continue;
+    };
+    let attributes_start = start + 1 + kind.tag_name().len();
+    let Some(open_tag_end) = find_tag_end(text, attributes_start) else {
+      break;
+    };
+    let attributes = &text[attributes_start..open_tag_end];
+    if attributes.trim_end().ends_with('/') {
+      // self-closing
+      pos = open_tag_end + 1;
+      continue;
+    }
+    let content_start = open_tag_end + 1;
+    let Some(content_end) = find_closing_tag(text, content_start, kind.tag_name()) else {
+      break;
+    };
+    if skipped_depth == 0 {
+      blocks.push(MarkupBlock {
+        kind,
+        attributes,
+        indent: get_line_indent(text, start),
+        content_range: content_start..content_end,
+      });
+    }
+    pos = content_end + 2;
+  }
+  blocks
+}
+
+fn starts_with_tag_name(text: &str, name: &str) -> bool {
+  text
+    .get(..name.len())
+    .is_some_and(|value| value.eq_ignore_ascii_case(name))
+    && text[name.len()..]
+      .chars()
+      .next()
+      .is_some_and(|c| c.is_ascii_whitespace() || c == '>' || c == '/')
+}
+
+/// Gets the position of the `>` that ends the tag, skipping over quoted attribute values.
+fn find_tag_end(text: &str, start: usize) -> Option<usize> {
+  let mut quote = None;
+  for (i, c) in text[start..].char_indices() {
+    match quote {
+      Some(quote_char) => {
+        if c == quote_char {
+          quote = None;
+        }
+      }
+      None => match c {
+        '"' | '\'' => quote = Some(c),
+        '>' => return Some(start + i),
+        _ => {}
+      },
+    }
+  }
+  None
+}
+
+/// Gets the position of the `</name` that closes the element.
+fn find_closing_tag(text: &str, start: usize, name: &str) -> Option<usize> {
+  let mut pos = start;
+  while let Some(offset) = text[pos..].find("</") {
+    let tag_start = pos + offset;
+    if starts_with_tag_name(&text[tag_start + 2..], name) {
+      return Some(tag_start);
+    }
+    pos = tag_start + 2;
+  }
+  None
+}
+
+/// Gets the value of an attribute in the raw attributes text of a tag.
+///
+/// Boolean attributes (ex. `setup`) have an empty value.
+pub fn get_attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+  let mut rest = attributes;
+  loop {
+    rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == '/');
+    if rest.is_empty() {
+      return None;
+    }
+    let name_end = rest
+      .find(|c: char| c.is_ascii_whitespace() || c == '=' || c == '/')
+      .unwrap_or(rest.len());
+    let attribute_name = &rest[..name_end];
+    rest = rest[name_end..].trim_start();
+    let value = if let Some(after_equals) = rest.strip_prefix('=') {
+      let after_equals = after_equals.trim_start();
+      match after_equals.chars().next() {
+        Some(quote @ ('"' | '\'')) => {
+          let value_text = &after_equals[1..];
+          let value_end = value_text.find(quote).unwrap_or(value_text.len());
+          rest = value_text.get(value_end + 1..).unwrap_or("");
+          &value_text[..value_end]
+        }
+        _ => {
+          let value_end = after_equals
+            .find(|c: char| c.is_ascii_whitespace())
+            .unwrap_or(after_equals.len());
+          rest = &after_equals[value_end..];
+          &after_equals[..value_end]
+        }
+      }
+    } else {
+      ""
+    };
+    if attribute_name.eq_ignore_ascii_case(name) {
+      return Some(value);
+    }
+  }
+}
@@ -0,0 +1,198 @@
+use dprint_core::configuration::ConfigKeyMap;
+use dprint_core::configuration::GlobalConfiguration;
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::configuration::Configuration;
+use crate::configuration::resolve_config;
+
+/// Resolves the configuration for the files matching several overrides from
+/// the keys of the overrides, caching it for each combination of overrides.
+pub struct OverridesResolver {
+  base_keys: ConfigKeyMap,
+  override_keys: Vec<ConfigKeyMap>,
+  global_config: GlobalConfiguration,
+  configs: Mutex<HashMap<Vec<usize>, Arc<Configuration>>>,
+}
+
+impl OverridesResolver {
+  pub fn new(base_keys: ConfigKeyMap, override_keys: Vec<ConfigKeyMap>, global_config: GlobalConfiguration) -> Self {
+    OverridesResolver {
+      base_keys,
+      override_keys,
+      global_config,
+      configs: Default::default(),
+    }
+  }
+
+  fn resolve(&self, override_indexes: &[usize]) -> Arc<Configuration> {
+    if let Some(config) = self.configs.lock().unwrap().get(override_indexes) {
+      return config.clone();
+    }
+    let mut keys = self.base_keys.clone();
+    for index in override_indexes {
+      keys.extend(self.override_keys[*index].clone());
+    }
+    // the diagnostics were reported when resolving each override
+    let config = Arc::new(resolve_config(keys, &self.global_config).config);
+    self
+      .configs
+      .lock()
+      .unwrap()
+      .insert(override_indexes.to_vec(), config.clone());
+    config
+  }
+}
+
+/// The configuration for a file.
+pub enum FileConfig<'a> {
+  Borrowed(&'a Configuration),
+  Shared(Arc<Configuration>),
+}
+
+impl Deref for FileConfig<'_> {
+  type Target = Configuration;
+
+  fn deref(&self) -> &Configuration {
+    match self {
+      FileConfig::Borrowed(config) => config,
+      FileConfig::Shared(config) => config,
+    }
+  }
+}
+
+/// Gets the configuration to use for the file path, which has the keys of
+/// every override with `includes` matching the path applied in order.
+///
+/// Globs are matched against the whole path provided by dprint, which is
+/// absolute, so globs need to start with `**/` to match in any directory.
+pub fn get_file_config<'a>(file_path: &Path, config: &'a Configuration) -> FileConfig<'a> {
+  if config.overrides.is_empty() {
+    return FileConfig::Borrowed(config);
+  }
+  let file_path = file_path.to_string_lossy().replace('\\', "/");
+  let components = get_path_components(&file_path);
+  let override_indexes = config
+    .overrides
+    .iter()
+    .enumerate()
+    .filter(|(_, config_override)| matches_includes(&config_override.includes, &components))
+    .map(|(index, _)| index)
+    .collect::<Vec<_>>();
+  match (override_indexes.as_slice(), &config.overrides_resolver) {
+    ([], _) => FileConfig::Borrowed(config),
+    (&[index], _) => FileConfig::Borrowed(&config.overrides[index].config),
+    (_, Some(resolver)) => FileConfig::Shared(resolver.resolve(&override_indexes)),
+    // not created with resolve_config, so use the last matching override
+    (&[.., index], None) => FileConfig::Borrowed(&config.overrides[index].config),
+  }
+}
+
+/// Later globs take precedence, so a negated glob (ex. `!**/*.test.ts`)
+/// excludes paths matched by the globs before it.
+fn matches_includes(includes: &[String], components: &[&str]) -> bool {
+  let mut is_match = false;
+  for include in includes {
+    match include.strip_prefix('!') {
+      Some(glob) => is_match = is_match && !matches_glob(glob, components),
+      None => is_match = is_match || matches_glob(include, components),
+    }
+  }
+  is_match
+}
+
+fn matches_glob(glob: &str, components: &[&str]) -> bool {
+  expand_braces(glob).iter().any(|glob| {
+    let pattern = get_path_components(glob);
+    matches_components(&pattern, components)
+  })
+}
+
+fn get_path_components(path: &str) -> Vec<&str> {
+  path
+    .split('/')
+    .filter(|component| !component.is_empty() && *component != ".")
+    .collect()
+}
+
+/// Expands the alternatives of a glob (ex. `*.{ts,tsx}` to `*.ts` and `*.tsx`).
+pub fn expand_braces(glob: &str) -> Vec<String> {
+  let Some(start) = glob.find('{') else {
+    return vec![glob.to_string()];
+  };
+  let Some(end) = glob[start..].find('}').map(|end| start + end) else {
+    return vec![glob.to_string()];
+  };
+  glob[start + 1..end]
+    .split(',')
+    .flat_map(|alternative| expand_braces(&format!("{}{}{}", &glob[..start], alternative, &glob[end + 1..])))
+    .collect()
+}
+
+fn matches_components(pattern: &[&str], components: &[&str]) -> bool {
+  match pattern.split_first() {
+    None => components.is_empty(),
+    Some((&"**", rest)) => (0..=components.len()).any(|start| matches_components(rest, &components[start..])),
+    Some((first, rest)) => match components.split_first() {
+      Some((component, components)) => matches_component(first, component) && matches_components(rest, components),
+      None => false,
+    },
+  }
+}
+
+/// Matches a single path component with support for `*` and `?`.
+fn matches_component(pattern: &str, text: &str) -> bool {
+  let pattern = pattern.chars().collect::<Vec<_>>();
+  let text = text.chars().collect::<Vec<_>>();
+  let mut pattern_index = 0;
+  let mut text_index = 0;
+  // the last `*` and the text index it's currently matched up to
+  let mut star: Option<(usize, usize)> = None;
+  while text_index < text.len() {
+    if pattern_index < pattern.len() && (pattern[pattern_index] == '?' || pattern[pattern_index] == text[text_index]) {
+      pattern_index += 1;
+      text_index += 1;
+    } else if pattern_index < pattern.len() && pattern[pattern_index] == '*' {
+      star = Some((pattern_index, text_index));
+      pattern_index += 1;
+    } else if let Some((star_pattern_index, star_text_index)) = star {
+      pattern_index = star_pattern_index + 1;
+      text_index = star_text_index + 1;
+      star = Some((star_pattern_index, text_index));
+    } else {
+      return false;
+    }
+  }
+  pattern[pattern_index..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn matches_globs() {
+    let matches = |glob: &str, path: &str| matches_glob(glob, &get_path_components(path));
+    assert!(matches("**/legacy/**", "/repo/packages/legacy/src/mod.ts"));
+    assert!(matches("./legacy/**/*.ts", "./legacy/mod.ts"));
+    assert!(matches("/repo/legacy/**/*.ts", "/repo/legacy/mod.ts"));
+    assert!(matches("**/*.{js,ts}", "/repo/src/mod.ts"));
+    assert!(matches("**/src/mod?.ts", "/repo/src/mod1.ts"));
+    assert!(!matches("**/legacy/*.ts", "/repo/legacy/src/mod.ts"));
+    assert!(!matches("**/*.{js,ts}", "/repo/src/mod.tsx"));
+    // globs are anchored, so a directory outside the repo doesn't match
+    assert!(!matches("legacy/**", "/home/legacy/repo/src/mod.ts"));
+    assert!(!matches("src/**", "/home/user/src/repo/lib/mod.ts"));
+    assert!(matches_includes(
+      &["**/src/**".to_string(), "!**/*.test.ts".to_string()],
+      &get_path_components("/repo/src/mod.ts")
+    ));
+    assert!(!matches_includes(
+      &["**/src/**".to_string(), "!**/*.test.ts".to_string()],
+      &get_path_components("/repo/src/mod.test.ts")
+    ));
+  }
+}
@@ -1,5 +1,7 @@
 use super::configuration::Configuration;
+use super::configuration::get_config_updates;
 use super::configuration::resolve_config;
+use super::format_text::GRAPHQL_FILE_EXTENSIONS;
 
 use dprint_core::configuration::ConfigKeyMap;
 use dprint_core::configuration::GlobalConfiguration;
@@ -23,23 +25,35 @@ impl SyncPluginHandler<Configuration> for BiomePluginHandler {
     global_config: &GlobalConfiguration,
   ) -> PluginResolveConfigurationResult<Configuration> {
     let result = resolve_config(config, global_config);
-    let mut file_extensions = vec![
-      "ts".to_string(),
-      "tsx".to_string(),
-      "cts".to_string(),
-      "mts".to_string(),
-      "js".to_string(),
-      "jsx".to_string(),
-      "cjs".to_string(),
-      "mjs".to_string(),
-      "json".to_string(),
-      "jsonc".to_string(),
-    ];
+    let mut file_extensions = Vec::new();
+    if result.config.typescript_enabled != Some(false) {
+      file_extensions.extend(["ts", "tsx", "cts", "mts"].map(|ext| ext.to_string()));
+    }
+    if result.config.javascript_enabled != Some(false) {
+      file_extensions.extend(["js", "jsx", "cjs", "mjs"].map(|ext| ext.to_string()));
+    }
+    if result.config.json_enabled != Some(false) {
+      file_extensions.extend(["json", "jsonc", "json5"].map(|ext| ext.to_string()));
+    }
     if result.config.css_enabled == Some(true) {
       file_extensions.push("css".to_string());
     }
     if result.config.graphql_enabled == Some(true) {
-      file_extensions.push("graphql".to_string());
+      file_extensions.extend(GRAPHQL_FILE_EXTENSIONS.iter().map(|ext| ext.to_string()));
+      file_extensions.extend(result.config.graphql_extensions.iter().cloned());
+    }
+    if result.config.html_enabled == Some(true) {
+      file_extensions.push("html".to_string());
+      file_extensions.push("htm".to_string());
+    }
+    if result.config.vue_enabled == Some(true) {
+      file_extensions.push("vue".to_string());
+    }
+    if result.config.svelte_enabled == Some(true) {
+      file_extensions.push("svelte".to_string());
+    }
+    if result.config.astro_enabled == Some(true) {
+      file_extensions.push("astro".to_string());
     }
     PluginResolveConfigurationResult {
       config: result.config,
@@ -51,8 +65,8 @@ impl SyncPluginHandler<Configuration> for BiomePluginHandler {
     }
   }
 
-  fn check_config_updates(&self, _message: CheckConfigUpdatesMessage) -> anyhow::Result<Vec<ConfigChange>> {
-    Ok(Vec::new())
+  fn check_config_updates(&self, message: CheckConfigUpdatesMessage) -> anyhow::Result<Vec<ConfigChange>> {
+    Ok(get_config_updates(&message.config))
   }
 
   fn plugin_info(&mut self) -> PluginInfo {
@@ -77,14 +91,25 @@ impl SyncPluginHandler<Configuration> for BiomePluginHandler {
   fn format(
     &mut self,
     request: SyncFormatRequest<Configuration>,
-    _format_with_host: impl FnMut(SyncHostFormatRequest) -> FormatResult,
+    mut format_with_host: impl FnMut(SyncHostFormatRequest) -> FormatResult,
   ) -> FormatResult {
-    if request.range.is_some() {
-      return Ok(None); // not implemented
-    }
-
     let text = String::from_utf8_lossy(&request.file_bytes);
-    let maybe_text = super::format_text(request.file_path, &text, request.config)?;
+    let maybe_text = match request.range {
+      Some(range) => super::format_range(request.file_path, &text, range, request.config)?,
+      None => super::format_text_with_host(request.file_path, &text, request.config, |file_path, file_text| {
+        let override_config = ConfigKeyMap::new();
+        let maybe_bytes = format_with_host(SyncHostFormatRequest {
+          file_path,
+          file_bytes: file_text.as_bytes(),
+          range: None,
+          override_config: &override_config,
+        })?;
+        match maybe_bytes {
+          Some(bytes) => Ok(Some(String::from_utf8(bytes)?)),
+          None => Ok(None),
+        }
+      })?,
+    };
     Ok(maybe_text.map(|t| t.into_bytes()))
   }
 }
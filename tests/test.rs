@@ -5,8 +5,13 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use dprint_core::configuration::*;
+use dprint_core::plugins::ConfigChangeKind;
+use dprint_core::plugins::ConfigChangePathItem;
 use dprint_development::*;
 use dprint_plugin_biome::configuration::Configuration;
+use dprint_plugin_biome::configuration::JsonTrailingCommas;
+use dprint_plugin_biome::configuration::get_config_updates;
+use dprint_plugin_biome::configuration::parse_biome_config;
 use dprint_plugin_biome::configuration::resolve_config;
 use dprint_plugin_biome::*;
 use pretty_assertions::assert_eq;
@@ -113,3 +118,546 @@ fn css_modules_config_resolves() {
   assert!(result.diagnostics.is_empty());
   assert_eq!(result.config.css_css_modules, Some(true));
 }
+
+#[test]
+fn formats_range_js() {
+  let config = Configuration::default();
+  let text = "const a  =  1;\nconst b  =  2;\nconst c  =  3;\n";
+  let result = format_range(&PathBuf::from("./file.ts"), text, 15..29, &config)
+    .unwrap()
+    .unwrap();
+  assert_eq!(result, "const a  =  1;\nconst b = 2;\nconst c  =  3;\n");
+}
+
+#[test]
+fn formats_range_json() {
+  let config = Configuration::default();
+  let text = "{\n\t\"a\":   [1,2],\n\t\"b\":   [3,4]\n}\n";
+  let result = format_range(&PathBuf::from("./file.json"), text, 3..15, &config)
+    .unwrap()
+    .unwrap();
+  assert_eq!(result, "{\n\t\"a\": [1, 2],\n\t\"b\":   [3,4]\n}\n");
+}
+
+#[test]
+fn should_fail_on_out_of_bounds_range() {
+  let config = Configuration::default();
+  let err = format_range(&PathBuf::from("./file.ts"), "const a = 1;\n", 0..100, &config).unwrap_err();
+  assert_eq!(err.to_string(), "Invalid range 0..100 for text of length 13.");
+}
+
+#[test]
+fn formats_tagged_templates_with_host() {
+  let mut config_map = ConfigKeyMap::new();
+  let mut embedded_languages = ConfigKeyMap::new();
+  embedded_languages.insert("sql".to_string(), ConfigKeyValue::from_str("sql"));
+  config_map.insert(
+    "embeddedLanguages".to_string(),
+    ConfigKeyValue::Object(embedded_languages),
+  );
+  let result = resolve_config(config_map, &GlobalConfiguration::default());
+  assert!(result.diagnostics.is_empty());
+
+  let mut file_paths = Vec::new();
+  let formatted = format_text_with_host(
+    &PathBuf::from("/src/file.ts"),
+    "function get() {\n  return sql`  select *\n    from users`;\n}\n",
+    &result.config,
+    |file_path, file_text| {
+      file_paths.push(file_path.to_path_buf());
+      Ok(Some(file_text.to_uppercase()))
+    },
+  )
+  .unwrap()
+  .unwrap();
+  assert_eq!(file_paths, vec![PathBuf::from("/src/file.ts.sql")]);
+  assert_eq!(
+    formatted,
+    "function get() {\n\treturn sql`\n\t\tSELECT *\n\t\t  FROM USERS\n\t`;\n}\n"
+  );
+}
+
+#[test]
+fn formats_vue_style_blocks_with_host() {
+  let mut config = Configuration::default();
+  config.vue_enabled = Some(true);
+  let mut file_paths = Vec::new();
+  let formatted = format_text_with_host(
+    &PathBuf::from("/src/file.vue"),
+    "<style lang=\"scss\">\n$a:   1px;\n</style>\n",
+    &config,
+    |file_path, file_text| {
+      file_paths.push(file_path.to_path_buf());
+      Ok(Some(file_text.replace("   ", " ")))
+    },
+  )
+  .unwrap()
+  .unwrap();
+  assert_eq!(file_paths, vec![PathBuf::from("/src/file.vue.scss")]);
+  assert_eq!(formatted, "<style lang=\"scss\">\n$a: 1px;\n</style>\n");
+}
+
+#[test]
+fn formats_configured_graphql_extensions() {
+  let mut config_map = ConfigKeyMap::new();
+  config_map.insert("graphql.enabled".to_string(), ConfigKeyValue::from_bool(true));
+  config_map.insert(
+    "graphql.extensions".to_string(),
+    ConfigKeyValue::Array(vec![ConfigKeyValue::from_str(".GraphQL5")]),
+  );
+  let result = resolve_config(config_map, &GlobalConfiguration::default());
+  assert!(result.diagnostics.is_empty());
+  assert_eq!(result.config.graphql_extensions, vec!["graphql5".to_string()]);
+
+  let text = "type   Query { hero: String }\n";
+  let expected = "type Query {\n\thero: String\n}\n";
+  for file_name in ["./file.graphqls", "./file.graphql5"] {
+    let formatted = format_text(&PathBuf::from(file_name), text, &result.config)
+      .unwrap()
+      .unwrap();
+    assert_eq!(formatted, expected);
+  }
+}
+
+#[test]
+fn resolves_biome_config() {
+  let biome_config = parse_biome_config(
+    r#"{
+  // comments are allowed
+  "formatter": { "enabled": true, "indentStyle": "space", "indentWidth": 4, "lineWidth": 100 },
+  "javascript": { "formatter": { "quoteStyle": "single", "semicolons": "asNeeded", "lineWidth": 80 } },
+  "json": { "formatter": { "enabled": false } },
+  "css": { "formatter": { "enabled": true } },
+}"#,
+  )
+  .unwrap();
+  assert_eq!(biome_config.len(), 4);
+
+  let mut config_map = ConfigKeyMap::new();
+  config_map.insert("lineWidth".to_string(), ConfigKeyValue::from_i32(120));
+  config_map.insert("biomeConfig".to_string(), ConfigKeyValue::Object(biome_config));
+  let result = resolve_config(config_map, &GlobalConfiguration::default());
+  assert!(result.diagnostics.is_empty());
+  // explicit keys take precedence, including general keys over language specific biome keys
+  assert_eq!(result.config.javascript_line_width, Some(120));
+  assert_eq!(result.config.css_line_width, Some(120));
+  assert_eq!(result.config.javascript_indent_width, Some(4));
+  assert_eq!(result.config.css_indent_width, Some(4));
+  assert_eq!(result.config.css_enabled, Some(true));
+  assert_eq!(result.config.json_enabled, Some(false));
+
+  let formatted = format_text(&PathBuf::from("./file.ts"), "const a = \"b\";\n", &result.config)
+    .unwrap()
+    .unwrap();
+  assert_eq!(formatted, "const a = 'b'\n");
+}
+
+#[test]
+fn resolves_overrides_over_biome_config() {
+  let mut override_config = ConfigKeyMap::new();
+  override_config.insert(
+    "includes".to_string(),
+    ConfigKeyValue::Array(vec![ConfigKeyValue::from_str("**/legacy/**")]),
+  );
+  override_config.insert("lineWidth".to_string(), ConfigKeyValue::from_i32(100));
+  let mut config_map = ConfigKeyMap::new();
+  config_map.insert(
+    "biomeConfig".to_string(),
+    ConfigKeyValue::Object(parse_biome_config(r#"{ "javascript": { "formatter": { "lineWidth": 80 } } }"#).unwrap()),
+  );
+  config_map.insert(
+    "overrides".to_string(),
+    ConfigKeyValue::Array(vec![ConfigKeyValue::Object(override_config)]),
+  );
+  let result = resolve_config(config_map, &GlobalConfiguration::default());
+  assert!(result.diagnostics.is_empty());
+  assert_eq!(result.config.javascript_line_width, Some(80));
+  assert_eq!(result.config.overrides[0].config.javascript_line_width, Some(100));
+}
+
+#[test]
+fn should_fail_on_invalid_biome_config() {
+  let err = parse_biome_config("[]").unwrap_err();
+  assert_eq!(err.to_string(), "Expected the Biome configuration to be an object.");
+}
+
+#[test]
+fn formats_with_overrides() {
+  let mut config_map = ConfigKeyMap::new();
+  config_map.insert("semicolons".to_string(), ConfigKeyValue::from_str("always"));
+  config_map.insert("quoteStyle".to_string(), ConfigKeyValue::from_str("single"));
+  let mut override_config = ConfigKeyMap::new();
+  override_config.insert(
+    "includes".to_string(),
+    ConfigKeyValue::Array(vec![ConfigKeyValue::from_str("**/legacy/**")]),
+  );
+  override_config.insert("semicolons".to_string(), ConfigKeyValue::from_str("asNeeded"));
+  config_map.insert(
+    "overrides".to_string(),
+    ConfigKeyValue::Array(vec![ConfigKeyValue::Object(override_config)]),
+  );
+  let result = resolve_config(config_map, &GlobalConfiguration::default());
+  assert!(result.diagnostics.is_empty());
+  assert_eq!(result.config.overrides.len(), 1);
+
+  let text = "const a = \"b\";\n";
+  let formatted = format_text(&PathBuf::from("/repo/src/file.ts"), text, &result.config)
+    .unwrap()
+    .unwrap();
+  assert_eq!(formatted, "const a = 'b';\n");
+  let formatted = format_text(&PathBuf::from("/repo/legacy/src/file.ts"), text, &result.config)
+    .unwrap()
+    .unwrap();
+  assert_eq!(formatted, "const a = 'b'\n");
+}
+
+#[test]
+fn formats_with_every_matching_override() {
+  let mut legacy_override = ConfigKeyMap::new();
+  legacy_override.insert(
+    "includes".to_string(),
+    ConfigKeyValue::Array(vec![ConfigKeyValue::from_str("**/legacy/**")]),
+  );
+  legacy_override.insert("lineWidth".to_string(), ConfigKeyValue::from_i32(20));
+  let mut test_override = ConfigKeyMap::new();
+  test_override.insert(
+    "includes".to_string(),
+    ConfigKeyValue::Array(vec![ConfigKeyValue::from_str("**/*.test.ts")]),
+  );
+  test_override.insert("semicolons".to_string(), ConfigKeyValue::from_str("asNeeded"));
+  let mut config_map = ConfigKeyMap::new();
+  config_map.insert(
+    "overrides".to_string(),
+    ConfigKeyValue::Array(vec![
+      ConfigKeyValue::Object(legacy_override),
+      ConfigKeyValue::Object(test_override),
+    ]),
+  );
+  let result = resolve_config(config_map, &GlobalConfiguration::default());
+  assert!(result.diagnostics.is_empty());
+
+  let text = "const a = { bbbbbb: 1, cccccc: 2 };\n";
+  let formatted = format_text(&PathBuf::from("/repo/src/file.test.ts"), text, &result.config)
+    .unwrap()
+    .unwrap();
+  assert_eq!(formatted, "const a = { bbbbbb: 1, cccccc: 2 }\n");
+  // the second file uses the configuration resolved for the first
+  for file_name in ["/repo/legacy/file.test.ts", "/repo/legacy/other.test.ts"] {
+    let formatted = format_text(&PathBuf::from(file_name), text, &result.config)
+      .unwrap()
+      .unwrap();
+    assert_eq!(formatted, "const a = {\n\tbbbbbb: 1,\n\tcccccc: 2,\n}\n");
+  }
+  // the keys used to resolve the overrides aren't part of the resolved configuration
+  let serialized = serde_json::to_string(&result.config).unwrap();
+  assert!(!serialized.contains("Resolver"));
+}
+
+#[test]
+fn reports_relative_override_globs() {
+  let mut override_config = ConfigKeyMap::new();
+  override_config.insert(
+    "includes".to_string(),
+    ConfigKeyValue::Array(vec![
+      ConfigKeyValue::from_str("legacy/**"),
+      ConfigKeyValue::from_str("**/legacy/**"),
+      ConfigKeyValue::from_str("!/repo/legacy/vendor/**"),
+    ]),
+  );
+  override_config.insert("lineWidth".to_string(), ConfigKeyValue::from_i32(100));
+  let mut config_map = ConfigKeyMap::new();
+  config_map.insert(
+    "overrides".to_string(),
+    ConfigKeyValue::Array(vec![ConfigKeyValue::Object(override_config)]),
+  );
+  let result = resolve_config(config_map, &GlobalConfiguration::default());
+  assert_eq!(result.diagnostics.len(), 1);
+  assert_eq!(result.diagnostics[0].property_name, "overrides[0].includes");
+  assert_eq!(
+    result.diagnostics[0].message,
+    "Expected the glob to start with **/ or be absolute because globs are matched against absolute file paths (ex. **/legacy/**), but found legacy/**."
+  );
+}
+
+#[test]
+fn reports_override_diagnostics() {
+  let mut config_map = ConfigKeyMap::new();
+  config_map.insert("unknownBase".to_string(), ConfigKeyValue::from_bool(true));
+  let mut override_config = ConfigKeyMap::new();
+  override_config.insert("lineWidth".to_string(), ConfigKeyValue::from_str("wide"));
+  config_map.insert(
+    "overrides".to_string(),
+    ConfigKeyValue::Array(vec![ConfigKeyValue::Object(override_config)]),
+  );
+  let result = resolve_config(config_map, &GlobalConfiguration::default());
+  let property_names = result
+    .diagnostics
+    .iter()
+    .map(|diagnostic| diagnostic.property_name.as_str())
+    .collect::<Vec<_>>();
+  assert_eq!(
+    property_names,
+    vec!["overrides[0].includes", "overrides[0].lineWidth", "unknownBase"]
+  );
+}
+
+#[test]
+fn formats_json_trailing_commas_only_where_allowed() {
+  let mut config = Configuration::default();
+  config.json_trailing_commas = Some(JsonTrailingCommas::All);
+  let text = "{\n  \"a\": 1\n}\n";
+  for file_name in ["./tsconfig.json", "./tsconfig.build.json", "./.vscode/settings.json"] {
+    let formatted = format_text(&PathBuf::from(file_name), text, &config).unwrap().unwrap();
+    assert_eq!(formatted, "{\n\t\"a\": 1,\n}\n", "{}", file_name);
+  }
+  let formatted = format_text(&PathBuf::from("./package.json"), text, &config)
+    .unwrap()
+    .unwrap();
+  assert_eq!(formatted, "{\n\t\"a\": 1\n}\n");
+}
+
+#[test]
+fn formats_with_language_line_endings() {
+  let mut config_map = ConfigKeyMap::new();
+  config_map.insert("lineEnding".to_string(), ConfigKeyValue::from_str("crlf"));
+  config_map.insert("css.enabled".to_string(), ConfigKeyValue::from_bool(true));
+  config_map.insert("graphql.enabled".to_string(), ConfigKeyValue::from_bool(true));
+  config_map.insert("json.lineEnding".to_string(), ConfigKeyValue::from_str("lf"));
+  let result = resolve_config(config_map, &GlobalConfiguration::default());
+  assert!(result.diagnostics.is_empty());
+
+  let cases = [
+    ("./file.css", "a { color: red; }\n", "a {\r\n\tcolor: red;\r\n}\r\n"),
+    ("./file.graphql", "type Query { a: String }\n", "type Query {\r\n\ta: String\r\n}\r\n"),
+    ("./file.ts", "const a = {\n  b: 1 };\n", "const a = {\r\n\tb: 1,\r\n};\r\n"),
+    ("./file.json", "{\r\n  \"a\": 1 }\r\n", "{\n\t\"a\": 1\n}\n"),
+  ];
+  for (file_name, text, expected) in cases {
+    let formatted = format_text(&PathBuf::from(file_name), text, &result.config)
+      .unwrap()
+      .unwrap();
+    assert_eq!(formatted, expected, "{}", file_name);
+  }
+}
+
+#[test]
+fn formats_with_line_ending_when_language_line_ending_unset() {
+  let mut config = Configuration::default();
+  config.line_ending = Some(dprint_plugin_biome::configuration::LineEnding::Crlf);
+  config.css_enabled = Some(true);
+  let cases = [
+    ("file.ts", "const a = {\n b: 1 };\n", "const a = {\r\n\tb: 1,\r\n};\r\n"),
+    ("file.json", "{\n\"a\": 1 }\n", "{\r\n\t\"a\": 1\r\n}\r\n"),
+    ("file.css", "a{color:red}\n", "a {\r\n\tcolor: red;\r\n}\r\n"),
+  ];
+  for (file_name, text, expected) in cases {
+    let formatted = format_text(&PathBuf::from(file_name), text, &config).unwrap().unwrap();
+    assert_eq!(formatted, expected, "{}", file_name);
+  }
+}
+
+#[test]
+fn formats_with_auto_line_ending() {
+  let mut config_map = ConfigKeyMap::new();
+  config_map.insert("lineEnding".to_string(), ConfigKeyValue::from_str("auto"));
+  let result = resolve_config(config_map, &GlobalConfiguration::default());
+  assert!(result.diagnostics.is_empty());
+
+  let cases = [
+    ("const a = {\r\n  b: 1 };\r\nconst c = 2;\n", "const a = {\r\n\tb: 1,\r\n};\r\nconst c = 2;\r\n"),
+    ("const a = {\n  b: 1 };\r\nconst c = 2;\n", "const a = {\n\tb: 1,\n};\nconst c = 2;\n"),
+    ("const a = 1", "const a = 1;\n"),
+  ];
+  for (text, expected) in cases {
+    let formatted = format_text(&PathBuf::from("./file.ts"), text, &result.config)
+      .unwrap()
+      .unwrap();
+    assert_eq!(formatted, expected);
+  }
+}
+
+#[test]
+fn reports_out_of_range_widths() {
+  let mut config_map = ConfigKeyMap::new();
+  config_map.insert("indentWidth".to_string(), ConfigKeyValue::from_i32(4));
+  config_map.insert("json.indentWidth".to_string(), ConfigKeyValue::from_i32(25));
+  config_map.insert("css.lineWidth".to_string(), ConfigKeyValue::from_i32(0));
+  config_map.insert("javascript.lineWidth".to_string(), ConfigKeyValue::from_i32(321));
+  let result = resolve_config(config_map, &GlobalConfiguration::default());
+  let diagnostics = result
+    .diagnostics
+    .iter()
+    .map(|diagnostic| format!("{}: {}", diagnostic.property_name, diagnostic.message))
+    .collect::<Vec<_>>();
+  assert_eq!(
+    diagnostics,
+    vec![
+      "css.lineWidth: Expected a value between 1 and 320, but found 0.",
+      "javascript.lineWidth: Expected a value between 1 and 320, but found 321.",
+      "json.indentWidth: Expected a value between 0 and 24, but found 25.",
+    ]
+  );
+  // falls back to the less specific value
+  assert_eq!(result.config.json_indent_width, Some(4));
+}
+
+#[test]
+fn resolves_renamed_keys() {
+  let mut config_map = ConfigKeyMap::new();
+  config_map.insert("indentSize".to_string(), ConfigKeyValue::from_i32(4));
+  config_map.insert("trailingComma".to_string(), ConfigKeyValue::from_str("none"));
+  config_map.insert("json.indentSize".to_string(), ConfigKeyValue::from_i32(8));
+  config_map.insert("json.indentWidth".to_string(), ConfigKeyValue::from_i32(2));
+  let result = resolve_config(config_map, &GlobalConfiguration::default());
+  // the old key is unknown when the new key is also specified
+  assert_eq!(result.diagnostics.len(), 1);
+  assert_eq!(result.diagnostics[0].property_name, "json.indentSize");
+  assert_eq!(result.config.javascript_indent_width, Some(4));
+  assert_eq!(result.config.json_indent_width, Some(2));
+  assert!(matches!(
+    result.config.trailing_commas,
+    Some(dprint_plugin_biome::configuration::TrailingComma::None)
+  ));
+}
+
+#[test]
+fn gets_config_updates_for_renamed_keys() {
+  let mut override_config = ConfigKeyMap::new();
+  override_config.insert(
+    "includes".to_string(),
+    ConfigKeyValue::Array(vec![ConfigKeyValue::from_str("**/*.json")]),
+  );
+  override_config.insert("json.indentSize".to_string(), ConfigKeyValue::from_i32(2));
+  let mut config_map = ConfigKeyMap::new();
+  config_map.insert("indentSize".to_string(), ConfigKeyValue::from_i32(4));
+  config_map.insert("trailingComma".to_string(), ConfigKeyValue::from_str("none"));
+  config_map.insert("trailingCommas".to_string(), ConfigKeyValue::from_str("all"));
+  config_map.insert(
+    "overrides".to_string(),
+    ConfigKeyValue::Array(vec![ConfigKeyValue::Object(override_config)]),
+  );
+
+  let changes = get_config_updates(&config_map)
+    .iter()
+    .map(|change| {
+      let path = change
+        .path
+        .iter()
+        .map(|item| match item {
+          ConfigChangePathItem::String(key) => key.clone(),
+          ConfigChangePathItem::Number(index) => index.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(".");
+      match &change.kind {
+        ConfigChangeKind::Add(ConfigKeyValue::Number(value)) => format!("add {} {}", path, value),
+        ConfigChangeKind::Remove => format!("remove {}", path),
+        _ => panic!("Unexpected change for {}.", path),
+      }
+    })
+    .collect::<Vec<_>>();
+  assert_eq!(
+    changes,
+    vec![
+      "add indentWidth 4",
+      "remove indentSize",
+      // trailingCommas is already specified, so only the old key is removed
+      "remove trailingComma",
+      "add overrides.0.json.indentWidth 2",
+      "remove overrides.0.json.indentSize",
+    ]
+  );
+}
+
+#[test]
+fn formats_with_editorconfig() {
+  let dir = std::env::temp_dir().join(format!("dprint-plugin-biome-editorconfig-{}", std::process::id()));
+  std::fs::create_dir_all(dir.join("src")).unwrap();
+  std::fs::write(
+    dir.join(".editorconfig"),
+    "root = true\n\n[*]\nindent_style = space\nindent_size = 4\nend_of_line = crlf\n\n[*.json]\nindent_size = 2\n",
+  )
+  .unwrap();
+  std::fs::write(dir.join("src/.editorconfig"), "[lib/**.ts]\nindent_style = tab\n").unwrap();
+
+  let mut config = Configuration::default();
+  config.editorconfig = Some(true);
+  config.json_line_ending = Some(dprint_plugin_biome::configuration::LineEnding::Lf);
+  let cases = [
+    ("src/file.ts", "const a = {\n b: 1 };\n", "const a = {\r\n    b: 1,\r\n};\r\n"),
+    ("src/lib/mod.ts", "const a = {\n b: 1 };\n", "const a = {\r\n\tb: 1,\r\n};\r\n"),
+    ("data.json", "{\n\"a\": 1 }\n", "{\n  \"a\": 1\n}\n"),
+  ];
+  for (file_name, text, expected) in cases {
+    let formatted = format_text(&dir.join(file_name), text, &config).unwrap().unwrap();
+    assert_eq!(formatted, expected, "{}", file_name);
+  }
+  std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn reads_editorconfig_files_once_per_configuration() {
+  let dir = std::env::temp_dir().join(format!("dprint-plugin-biome-editorconfig-cache-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  std::fs::write(dir.join(".editorconfig"), "root = true\n\n[*]\nindent_style = space\n").unwrap();
+
+  let mut config = Configuration::default();
+  config.editorconfig = Some(true);
+  let text = "const a = {\n b: 1 };\n";
+  let expected = "const a = {\n  b: 1,\n};\n";
+  assert_eq!(format_text(&dir.join("a.ts"), text, &config).unwrap().unwrap(), expected);
+  // the parsed file is reused after it's removed
+  std::fs::remove_dir_all(&dir).unwrap();
+  assert_eq!(format_text(&dir.join("b.ts"), text, &config).unwrap().unwrap(), expected);
+}
+
+#[test]
+fn ignores_out_of_range_editorconfig_widths() {
+  let dir = std::env::temp_dir().join(format!("dprint-plugin-biome-editorconfig-range-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  std::fs::write(
+    dir.join(".editorconfig"),
+    "root = true\n\n[*]\nindent_style = space\nindent_size = 2\nmax_line_length = 40\n\n[*.ts]\nindent_size = 30\nmax_line_length = 400\n",
+  )
+  .unwrap();
+
+  let mut config = Configuration::default();
+  config.editorconfig = Some(true);
+  let text = "const value = { first: 1, second: 2, third: 3 };\n";
+  let formatted = format_text(&dir.join("file.ts"), text, &config).unwrap().unwrap();
+  assert_eq!(formatted, "const value = {\n  first: 1,\n  second: 2,\n  third: 3,\n};\n");
+  std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn should_fail_on_parameter_decorators_when_disabled() {
+  let text = "class A {\n\tconstructor(@Inject() service: Service) {}\n}\n";
+  let mut config = Configuration::default();
+  assert!(format_text(&PathBuf::from("./file.ts"), text, &config).is_ok());
+  config.javascript_parser_unsafe_parameter_decorators_enabled = Some(false);
+  assert!(format_text(&PathBuf::from("./file.ts"), text, &config).is_err());
+}
+
+#[test]
+fn should_fail_on_jsx_in_cjs_files_unless_enabled() {
+  let text = "module.exports = () => <div className=\"app\">Hello</div>;\n";
+  let mut config = Configuration::default();
+  assert!(format_text(&PathBuf::from("./file.cjs"), text, &config).is_err());
+  config.javascript_jsx_everywhere = Some(false);
+  assert!(format_text(&PathBuf::from("./file.cjs"), text, &config).is_err());
+  config.javascript_jsx_everywhere = Some(true);
+  assert!(format_text(&PathBuf::from("./file.cjs"), text, &config).is_ok());
+}
+
+#[test]
+fn should_fail_on_json5_non_values() {
+  let config = Configuration::default();
+  let file_path = PathBuf::from("./file.json5");
+  for text in ["{a: foo()}", "{a: `b`}", "{[a]: 1}", "{a}", "[1, -(2)]", "1) + (2", "undefined"] {
+    assert!(format_text(&file_path, text, &config).is_err(), "{}", text);
+  }
+  let formatted = format_text(&file_path, "{a: -Infinity, b: NaN, c: +1, 'd': null}", &config)
+    .unwrap()
+    .unwrap();
+  assert_eq!(formatted, "{ a: -Infinity, b: NaN, c: +1, \"d\": null }\n");
+}